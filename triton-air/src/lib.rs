@@ -61,6 +61,17 @@ pub trait AIR: private::Seal {
     fn terminal_constraints(
         circuit_builder: &ConstraintCircuitBuilder<SingleRowIndicator>,
     ) -> Vec<ConstraintCircuitMonad<SingleRowIndicator>>;
+
+    /// The highest [degree](constraint_circuit::ConstraintCircuit::degree) among
+    /// this table's [transition constraints](Self::transition_constraints), before
+    /// degree lowering, or `None` if the table has no transition constraints.
+    fn max_transition_constraint_degree() -> Option<isize> {
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        Self::transition_constraints(&circuit_builder)
+            .into_iter()
+            .map(|constraint| constraint.consume().degree())
+            .max()
+    }
 }
 
 #[cfg(test)]
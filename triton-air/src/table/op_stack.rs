@@ -197,3 +197,62 @@ impl AIR for OpStackTable {
         vec![]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use constraint_circuit::CircuitExpression;
+    use constraint_circuit::ConstraintCircuit;
+    use constraint_circuit::InputIndicator;
+    use itertools::Itertools;
+
+    use crate::table_column::OpStackAuxColumn;
+
+    use super::*;
+
+    /// Whether the circuit reads the given input anywhere.
+    fn reads<II: InputIndicator>(circuit: &ConstraintCircuit<II>, input: II) -> bool {
+        match &circuit.expression {
+            CircuitExpression::Input(circuit_input) => *circuit_input == input,
+            CircuitExpression::BinOp(_, lhs, rhs) => {
+                reads(&lhs.borrow(), input) || reads(&rhs.borrow(), input)
+            }
+            _ => false,
+        }
+    }
+
+    /// The degree of the only transition constraint that reads the given auxiliary
+    /// column in the next row.
+    fn degree_of_transition_constraint_updating(column: OpStackAuxColumn) -> isize {
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        let next_aux_row = NextAux(column.master_aux_index());
+        let constraints = OpStackTable::transition_constraints(&circuit_builder)
+            .into_iter()
+            .map(|constraint| constraint.consume())
+            .filter(|constraint| reads(constraint, next_aux_row))
+            .collect_vec();
+        let [constraint] = constraints.as_slice() else {
+            panic!("expected exactly one constraint updating {column}");
+        };
+        constraint.degree()
+    }
+
+    #[test]
+    fn running_product_permutation_argument_update_has_degree_3() {
+        let rppa_updates_correctly =
+            degree_of_transition_constraint_updating(OpStackAuxColumn::RunningProductPermArg);
+        assert_eq!(3, rppa_updates_correctly);
+    }
+
+    #[test]
+    fn clock_jump_difference_log_derivative_update_has_degree_4() {
+        let log_derivative_updates_correctly = degree_of_transition_constraint_updating(
+            OpStackAuxColumn::ClockJumpDifferenceLookupClientLogDerivative,
+        );
+        assert_eq!(4, log_derivative_updates_correctly);
+    }
+
+    #[test]
+    fn max_transition_constraint_degree_is_bottleneck_constraint_degree() {
+        assert_eq!(Some(4), OpStackTable::max_transition_constraint_degree());
+    }
+}
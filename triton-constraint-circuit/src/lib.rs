@@ -475,6 +475,36 @@ impl<II: InputIndicator> ConstraintCircuit<II> {
         }
     }
 
+    /// Render the circuit as a human-readable expression. In contrast to [`Display`],
+    /// inputs and challenges are resolved to names using the supplied functions, and
+    /// only compound operands are wrapped in parentheses.
+    pub fn to_string_with_names<I, C>(&self, input_name: &I, challenge_name: &C) -> String
+    where
+        I: Fn(II) -> String,
+        C: Fn(usize) -> String,
+    {
+        let operand_to_string = |operand: &Rc<RefCell<Self>>| {
+            let operand = operand.borrow();
+            let operand_string = operand.to_string_with_names(input_name, challenge_name);
+            match operand.expression {
+                CircuitExpression::BinOp(..) => format!("({operand_string})"),
+                _ => operand_string,
+            }
+        };
+
+        match &self.expression {
+            CircuitExpression::BConst(bfe) => bfe.to_string(),
+            CircuitExpression::XConst(xfe) => xfe.to_string(),
+            CircuitExpression::Input(input) => input_name(*input),
+            CircuitExpression::Challenge(challenge) => challenge_name(*challenge),
+            CircuitExpression::BinOp(operation, lhs, rhs) => {
+                let lhs = operand_to_string(lhs);
+                let rhs = operand_to_string(rhs);
+                format!("{lhs} {operation} {rhs}")
+            }
+        }
+    }
+
     /// All unique reference counters in the subtree, sorted.
     pub fn all_ref_counters(&self) -> Vec<usize> {
         let mut ref_counters = vec![self.ref_count];
@@ -531,11 +561,29 @@ impl<II: InputIndicator> ConstraintCircuit<II> {
         }
     }
 
+    /// Evaluate the circuit by interpreting it on the given rows and challenges.
+    /// Considerably slower than the generated constraint evaluation code, but
+    /// available for any circuit.
+    ///
+    /// Subexpressions shared between several nodes of the circuit are evaluated only
+    /// once. Relies on the IDs of all nodes in the circuit being unique, which is
+    /// guaranteed for circuits built using a [`ConstraintCircuitBuilder`].
     pub fn evaluate(
         &self,
         main_table: ArrayView2<BFieldElement>,
         aux_table: ArrayView2<XFieldElement>,
         challenges: &[XFieldElement],
+    ) -> XFieldElement {
+        let mut evaluated_nodes = HashMap::new();
+        self.evaluate_memoized(main_table, aux_table, challenges, &mut evaluated_nodes)
+    }
+
+    fn evaluate_memoized(
+        &self,
+        main_table: ArrayView2<BFieldElement>,
+        aux_table: ArrayView2<XFieldElement>,
+        challenges: &[XFieldElement],
+        evaluated_nodes: &mut HashMap<usize, XFieldElement>,
     ) -> XFieldElement {
         match &self.expression {
             CircuitExpression::BConst(bfe) => bfe.lift(),
@@ -543,9 +591,24 @@ impl<II: InputIndicator> ConstraintCircuit<II> {
             CircuitExpression::Input(input) => input.evaluate(main_table, aux_table),
             CircuitExpression::Challenge(challenge_id) => challenges[*challenge_id],
             CircuitExpression::BinOp(binop, lhs, rhs) => {
-                let lhs_value = lhs.borrow().evaluate(main_table, aux_table, challenges);
-                let rhs_value = rhs.borrow().evaluate(main_table, aux_table, challenges);
-                binop.operation(lhs_value, rhs_value)
+                if let Some(&value) = evaluated_nodes.get(&self.id) {
+                    return value;
+                }
+                let lhs_value = lhs.borrow().evaluate_memoized(
+                    main_table,
+                    aux_table,
+                    challenges,
+                    evaluated_nodes,
+                );
+                let rhs_value = rhs.borrow().evaluate_memoized(
+                    main_table,
+                    aux_table,
+                    challenges,
+                    evaluated_nodes,
+                );
+                let value = binop.operation(lhs_value, rhs_value);
+                evaluated_nodes.insert(self.id, value);
+                value
             }
         }
     }
@@ -1401,6 +1464,29 @@ mod tests {
         assert_eq!("(4·x² + 3·x + 2)", xfe_str);
     }
 
+    #[test]
+    fn printing_constraint_circuit_with_names_gives_expected_string() {
+        let builder = ConstraintCircuitBuilder::new();
+        let input = |i| builder.input(DualRowIndicator::CurrentMain(i));
+        let next_input = |i| builder.input(DualRowIndicator::NextMain(i));
+        let circuit = builder.challenge(1_usize) * (next_input(0) - input(0) - builder.one())
+            + builder.b_constant(2) * input(1);
+
+        let input_name = |input: DualRowIndicator| match input {
+            DualRowIndicator::CurrentMain(i) => format!("col_{i}"),
+            DualRowIndicator::NextMain(i) => format!("col_{i}'"),
+            _ => unreachable!(),
+        };
+        let challenge_name = |challenge| format!("challenge_{challenge}");
+        let circuit_string = circuit
+            .circuit
+            .borrow()
+            .to_string_with_names(&input_name, &challenge_name);
+
+        let expected = "(challenge_1 * ((col_0' + (-1 * col_0)) + -1)) + (2 * col_1)";
+        assert_eq!(expected, circuit_string);
+    }
+
     #[proptest]
     fn constant_folding_can_deal_with_multiplication_by_one(
         #[strategy(arb())] c: ConstraintCircuitMonad<DualRowIndicator>,
@@ -1461,6 +1547,31 @@ mod tests {
         prop_assert_ne!(&circuit, &zero_minus_circuit);
     }
 
+    #[test]
+    fn evaluating_circuit_with_many_shared_subexpressions_is_fast() {
+        let builder = ConstraintCircuitBuilder::new();
+        let mut circuit = builder.input(SingleRowIndicator::Main(0));
+
+        // without memoization, evaluation requires 2^64 leaf evaluations
+        let num_squarings = 64;
+        for _ in 0..num_squarings {
+            circuit = circuit.clone() * circuit;
+        }
+
+        let main_row = Array2::from_shape_vec([1, 1], bfe_vec![3]).unwrap();
+        let aux_row = Array2::zeros([1, 0]);
+        let evaluation = circuit
+            .circuit
+            .borrow()
+            .evaluate(main_row.view(), aux_row.view(), &[]);
+
+        let mut expected = bfe!(3);
+        for _ in 0..num_squarings {
+            expected *= expected;
+        }
+        assert_eq!(expected.lift(), evaluation);
+    }
+
     #[test]
     fn pointer_redirection_obliviates_a_node_in_a_circuit() {
         let builder = ConstraintCircuitBuilder::new();
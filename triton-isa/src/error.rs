@@ -1,7 +1,9 @@
 pub use crate::instruction::AssertionError;
 pub use crate::instruction::InstructionError;
+pub use crate::instruction::ParseInstructionError;
 pub use crate::op_stack::NumberOfWordsError;
 pub use crate::op_stack::OpStackElementError;
 pub use crate::op_stack::OpStackError;
 pub use crate::parser::ParseError;
+pub use crate::program::ProgramBytesDecodingError;
 pub use crate::program::ProgramDecodingError;
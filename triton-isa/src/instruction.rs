@@ -4,6 +4,7 @@ use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::num::TryFromIntError;
 use std::result;
+use std::str::FromStr;
 
 use arbitrary::Arbitrary;
 use arbitrary::Unstructured;
@@ -97,6 +98,27 @@ lazy_static! {
     };
 }
 
+/// A mnemonic of Triton assembly together with the number of arguments it expects. Useful
+/// for offering completions in interactive front-ends. See [`all_mnemonics`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MnemonicInfo {
+    pub mnemonic: &'static str,
+
+    /// Either 0 or 1. For example, `push` expects 1 argument, `add` expects none.
+    pub num_arguments: usize,
+}
+
+/// All mnemonics of Triton assembly, in the order of [`ALL_INSTRUCTIONS`].
+pub fn all_mnemonics() -> Vec<MnemonicInfo> {
+    ALL_INSTRUCTIONS
+        .iter()
+        .map(|instruction| MnemonicInfo {
+            mnemonic: instruction.name(),
+            num_arguments: instruction.size() - 1,
+        })
+        .collect()
+}
+
 /// A `LabelledInstruction` has `call` addresses encoded as label names.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, EnumCount)]
 pub enum LabelledInstruction {
@@ -617,6 +639,88 @@ impl Instruction {
 
         Ok(new_instruction)
     }
+
+    /// Parse a single instruction like [`from_str`](Self::from_str), but
+    /// additionally interpret a bare integer `N` as `push N`. Useful for interactive
+    /// exploration, where pushing values is the most common operation.
+    ///
+    /// Input starting with a digit or a minus sign is treated as a number, never as
+    /// a mnemonic. If it is not a valid field element, for example because it is
+    /// out of range, parsing fails with
+    /// [`InvalidArgument`](ParseInstructionError::InvalidArgument).
+    pub fn parse_with_implicit_push(s: &str) -> result::Result<Self, ParseInstructionError> {
+        let mut tokens = s.split_whitespace();
+        let Some(first_token) = tokens.next() else {
+            return Err(ParseInstructionError::Empty);
+        };
+        let is_number = first_token.starts_with(|c: char| c.is_ascii_digit() || c == '-');
+        if !is_number {
+            return s.parse();
+        }
+
+        let value = parse_instruction_argument(first_token)
+            .ok_or_else(|| ParseInstructionError::InvalidArgument(first_token.to_string()))?;
+
+        let trailing_input = tokens.collect_vec();
+        if !trailing_input.is_empty() {
+            return Err(ParseInstructionError::TrailingInput(
+                trailing_input.join(" "),
+            ));
+        }
+
+        Ok(Instruction::Push(value))
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ParseInstructionError;
+
+    /// Parse a single instruction, including its argument, if any.
+    ///
+    /// This is the inverse of [`Display`]. Since an [`Instruction`] refers to
+    /// `call` targets by their absolute address, `call` requires a numeric
+    /// argument, not a label.
+    fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace();
+        let Some(name) = tokens.next() else {
+            return Err(ParseInstructionError::Empty);
+        };
+        let Some(instruction) = ALL_INSTRUCTIONS.into_iter().find(|i| i.name() == name) else {
+            return Err(ParseInstructionError::UnknownInstruction(name.to_string()));
+        };
+
+        let instruction = if instruction.arg().is_some() {
+            let Some(arg) = tokens.next() else {
+                return Err(ParseInstructionError::MissingArgument(instruction));
+            };
+            let arg = parse_instruction_argument(arg)
+                .ok_or_else(|| ParseInstructionError::InvalidArgument(arg.to_string()))?;
+            instruction
+                .change_arg(arg)
+                .map_err(|_| ParseInstructionError::IllegalArgument(instruction, arg))?
+        } else {
+            instruction
+        };
+
+        let trailing_input = tokens.collect_vec();
+        if !trailing_input.is_empty() {
+            return Err(ParseInstructionError::TrailingInput(
+                trailing_input.join(" "),
+            ));
+        }
+
+        Ok(instruction)
+    }
+}
+
+/// Parse a possibly negative integer into a [`BFieldElement`]. Returns `None`
+/// if the string is not an integer or if its absolute value is not a canonical
+/// representative of a field element.
+fn parse_instruction_argument(arg: &str) -> Option<BFieldElement> {
+    match arg.strip_prefix('-') {
+        Some(absolute_value) => absolute_value.parse().ok().map(|e: BFieldElement| -e),
+        None => arg.parse().ok(),
+    }
 }
 
 impl TryFrom<u32> for Instruction {
@@ -822,6 +926,9 @@ pub enum InstructionError {
     #[error("instruction pointer points outside of program")]
     InstructionPointerOverflow,
 
+    #[error("jump target {0} is the argument of an instruction, not the start of one")]
+    MisalignedJump(BFieldElement),
+
     #[error("jump stack is empty")]
     JumpStackIsEmpty,
 
@@ -852,6 +959,9 @@ pub enum InstructionError {
     #[error("no more secret digests available")]
     EmptySecretDigestInput,
 
+    #[error("RAM address {0} is read-only")]
+    WriteToReadOnlyMemory(BFieldElement),
+
     #[error("Triton VM has halted and cannot execute any further instructions")]
     MachineHalted,
 
@@ -859,6 +969,29 @@ pub enum InstructionError {
     OpStackError(#[from] OpStackError),
 }
 
+/// An error that can occur when [parsing](FromStr) a single [`Instruction`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum ParseInstructionError {
+    #[error("expected an instruction, found nothing")]
+    Empty,
+
+    #[error("unknown instruction `{0}`")]
+    UnknownInstruction(String),
+
+    #[error("instruction `{}` requires an argument", .0.name())]
+    MissingArgument(Instruction),
+
+    #[error("argument `{0}` is not a valid field element")]
+    InvalidArgument(String),
+
+    #[error("invalid argument {1} for instruction `{name}`", name = .0.name())]
+    IllegalArgument(Instruction, BFieldElement),
+
+    #[error("unexpected input after instruction: `{0}`")]
+    TrailingInput(String),
+}
+
 /// An error giving additional context to any failed assertion.
 #[non_exhaustive]
 #[derive(Debug, Clone, Eq, PartialEq, Error)]
@@ -908,14 +1041,18 @@ pub mod tests {
     use std::collections::HashMap;
 
     use assert2::assert;
+    use assert2::let_assert;
     use itertools::Itertools;
     use num_traits::One;
     use num_traits::Zero;
+    use proptest::prelude::*;
+    use proptest_arbitrary_interop::arb;
     use rand::thread_rng;
     use rand::Rng;
     use strum::EnumCount;
     use strum::IntoEnumIterator;
     use strum::VariantNames;
+    use test_strategy::proptest;
     use twenty_first::prelude::*;
 
     use crate::triton_asm;
@@ -1197,4 +1334,124 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn parsing_printed_instruction_gives_original_instruction() {
+        for instruction in ALL_INSTRUCTIONS {
+            let parsed = instruction.to_string().parse::<Instruction>();
+            assert!(Ok(instruction) == parsed);
+        }
+    }
+
+    #[proptest]
+    fn parsing_printed_arbitrary_instruction_gives_original_instruction(
+        #[strategy(arb())] instruction: Instruction,
+    ) {
+        let parsed = instruction.to_string().parse::<Instruction>();
+        prop_assert_eq!(Ok(instruction), parsed);
+    }
+
+    #[test]
+    fn parse_instructions_with_negative_and_large_arguments() {
+        let push_minus_one = "push -1".parse::<Instruction>();
+        assert!(Ok(Instruction::Push(bfe!(-1))) == push_minus_one);
+
+        let large_address = BFieldElement::MAX.to_string();
+        let call = format!("call {large_address}").parse::<Instruction>();
+        assert!(Ok(Instruction::Call(bfe!(BFieldElement::MAX))) == call);
+    }
+
+    #[test]
+    fn parsing_malformed_instructions_gives_descriptive_errors() {
+        let_assert!(Err(ParseInstructionError::Empty) = "  ".parse::<Instruction>());
+
+        let_assert!(Err(err) = "foo".parse::<Instruction>());
+        assert!(ParseInstructionError::UnknownInstruction("foo".to_string()) == err);
+
+        let_assert!(Err(err) = "dup".parse::<Instruction>());
+        assert!(
+            ParseInstructionError::MissingArgument(Instruction::Dup(OpStackElement::ST0)) == err
+        );
+
+        let_assert!(Err(err) = "push x".parse::<Instruction>());
+        assert!(ParseInstructionError::InvalidArgument("x".to_string()) == err);
+
+        let_assert!(Err(err) = format!("push {}", BFieldElement::P).parse::<Instruction>());
+        let_assert!(ParseInstructionError::InvalidArgument(_) = err);
+
+        let_assert!(Err(err) = "dup 16".parse::<Instruction>());
+        let_assert!(ParseInstructionError::IllegalArgument(Instruction::Dup(_), arg) = err);
+        assert!(bfe!(16) == arg);
+
+        let_assert!(Err(err) = "pop 0".parse::<Instruction>());
+        let_assert!(ParseInstructionError::IllegalArgument(Instruction::Pop(_), _) = err);
+
+        let_assert!(Err(err) = "halt 1".parse::<Instruction>());
+        assert!(ParseInstructionError::TrailingInput("1".to_string()) == err);
+    }
+
+    #[test]
+    fn bare_numbers_are_parsed_as_push() {
+        let_assert!(Ok(instruction) = Instruction::parse_with_implicit_push(" 42 "));
+        assert!(Instruction::Push(bfe!(42)) == instruction);
+
+        let_assert!(Ok(instruction) = Instruction::parse_with_implicit_push("-1"));
+        assert!(Instruction::Push(bfe!(-1)) == instruction);
+
+        let max = BFieldElement::MAX.to_string();
+        let_assert!(Ok(instruction) = Instruction::parse_with_implicit_push(&max));
+        assert!(Instruction::Push(bfe!(BFieldElement::MAX)) == instruction);
+    }
+
+    #[test]
+    fn mnemonics_are_parsed_as_usual_when_bare_numbers_are_allowed() {
+        for instruction in ALL_INSTRUCTIONS {
+            let parsed = Instruction::parse_with_implicit_push(&instruction.to_string());
+            assert!(Ok(instruction) == parsed);
+        }
+    }
+
+    #[test]
+    fn malformed_bare_numbers_give_descriptive_errors() {
+        let out_of_range = BFieldElement::P.to_string();
+        let_assert!(Err(err) = Instruction::parse_with_implicit_push(&out_of_range));
+        assert!(ParseInstructionError::InvalidArgument(out_of_range) == err);
+
+        let_assert!(Err(err) = Instruction::parse_with_implicit_push("12ab"));
+        assert!(ParseInstructionError::InvalidArgument("12ab".to_string()) == err);
+
+        let_assert!(Err(err) = Instruction::parse_with_implicit_push("1 2"));
+        assert!(ParseInstructionError::TrailingInput("2".to_string()) == err);
+
+        let_assert!(Err(err) = Instruction::parse_with_implicit_push("foo"));
+        assert!(ParseInstructionError::UnknownInstruction("foo".to_string()) == err);
+
+        let_assert!(Err(ParseInstructionError::Empty) = Instruction::parse_with_implicit_push(""));
+    }
+
+    #[test]
+    fn mnemonics_cover_the_entire_instruction_set() {
+        let mnemonics = all_mnemonics();
+        assert!(Instruction::COUNT == mnemonics.len());
+
+        let names = mnemonics.iter().map(|info| info.mnemonic).collect_vec();
+        assert!(ALL_INSTRUCTION_NAMES.to_vec() == names);
+    }
+
+    #[test]
+    fn mnemonics_have_correct_number_of_arguments() {
+        let num_arguments = |mnemonic| {
+            let info = all_mnemonics().into_iter().find(|i| i.mnemonic == mnemonic);
+            info.unwrap().num_arguments
+        };
+
+        for mnemonic in [
+            "push", "pop", "divine", "dup", "swap", "call", "addi", "read_io",
+        ] {
+            assert!(1 == num_arguments(mnemonic), "{mnemonic}");
+        }
+        for mnemonic in ["halt", "nop", "add", "return", "hash", "xx_dot_step"] {
+            assert!(0 == num_arguments(mnemonic), "{mnemonic}");
+        }
+    }
 }
@@ -339,11 +339,14 @@ mod tests {
         implements_auto_traits::<error::OpStackElementError>();
         implements_auto_traits::<error::OpStackError>();
         implements_auto_traits::<error::ParseError>();
+        implements_auto_traits::<error::ParseInstructionError>();
+        implements_auto_traits::<error::ProgramBytesDecodingError>();
         implements_auto_traits::<error::ProgramDecodingError>();
 
         implements_auto_traits::<instruction::Instruction>();
         implements_auto_traits::<instruction::AnInstruction<usize>>();
         implements_auto_traits::<instruction::InstructionBit>();
+        implements_auto_traits::<instruction::MnemonicInfo>();
         implements_auto_traits::<instruction::TypeHint>();
 
         implements_auto_traits::<op_stack::NumberOfWords>();
@@ -355,5 +358,7 @@ mod tests {
 
         implements_auto_traits::<program::InstructionIter>();
         implements_auto_traits::<program::Program>();
+        implements_auto_traits::<program::EqualityOptions>();
+        implements_auto_traits::<program::InstructionDiff>();
     }
 }
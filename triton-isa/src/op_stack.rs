@@ -150,6 +150,19 @@ impl OpStack {
         xfe!([self[0], self[1], self[2]])
     }
 
+    /// The element at the given depth, where depth 0 is the top of the stack, or `None`
+    /// if the stack is not that deep. Unlike [indexing](Index), never panics.
+    pub fn peek(&self, depth: usize) -> Option<BFieldElement> {
+        let index = self.len().checked_sub(depth)?.checked_sub(1)?;
+        self.stack.get(index).copied()
+    }
+
+    /// The `n` topmost elements, starting with the top of the stack. Yields fewer
+    /// than `n` elements if the stack is not that deep.
+    pub fn top_n(&self, n: usize) -> impl Iterator<Item = BFieldElement> + '_ {
+        self.stack.iter().rev().take(n).copied()
+    }
+
     pub fn would_be_too_shallow(&self, stack_delta: i32) -> bool {
         self.len() as i32 + stack_delta < OpStackElement::COUNT as i32
     }
@@ -297,6 +310,12 @@ impl UnderflowIO {
 }
 
 /// Represents the [`OpStack`] registers directly accessible by Triton VM.
+///
+/// Iterating over all variants, for example through
+/// [`OpStackElement::iter()`](IntoEnumIterator::iter), yields them in stack
+/// order, starting with the top of the stack, [`ST0`](Self::ST0).
+/// Conversely, a depth into the stack can be converted into the corresponding
+/// variant using [`TryFrom<usize>`](#impl-TryFrom<usize>-for-OpStackElement).
 #[derive(
     Debug,
     Default,
@@ -365,6 +384,22 @@ impl OpStackElement {
             OpStackElement::ST15 => 15,
         }
     }
+
+    /// The name of the register this element refers to, for example, `st0` for the top of
+    /// the stack.
+    pub fn register_name(self) -> String {
+        format!("st{}", self.index())
+    }
+
+    /// A label for the given index into the op stack, where index 0 refers to the top of
+    /// the stack. The label is the [register name](Self::register_name) if the index
+    /// refers to a register, or the index itself otherwise.
+    pub fn label_for_stack_index(stack_index: usize) -> String {
+        match Self::try_from(stack_index) {
+            Ok(element) => element.register_name(),
+            Err(_) => stack_index.to_string(),
+        }
+    }
 }
 
 impl Display for OpStackElement {
@@ -696,6 +731,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn peeking_agrees_with_indexing() {
+        let mut op_stack = OpStack::default();
+        for i in 1..=5 {
+            op_stack.push(bfe!(i));
+        }
+        for depth in 0..op_stack.len() {
+            assert!(Some(op_stack[depth]) == op_stack.peek(depth));
+        }
+        assert!(Some(bfe!(5)) == op_stack.peek(0));
+    }
+
+    #[test]
+    fn peeking_past_the_bottom_of_the_stack_gives_none() {
+        let op_stack = OpStack::default();
+        assert!(None == op_stack.peek(op_stack.len()));
+        assert!(None == op_stack.peek(usize::MAX));
+    }
+
+    #[test]
+    fn top_n_elements_start_at_top_of_stack() {
+        let mut op_stack = OpStack::default();
+        for i in 1..=3 {
+            op_stack.push(bfe!(i));
+        }
+        assert!(bfe_vec![3, 2, 1] == op_stack.top_n(3).collect_vec());
+        assert!(op_stack.top_n(0).next().is_none());
+
+        let all_elements = op_stack.top_n(usize::MAX).collect_vec();
+        assert!(op_stack.clone().into_iter().collect_vec() == all_elements);
+    }
+
     #[test]
     fn sanity() {
         let mut op_stack = OpStack::default();
@@ -949,6 +1016,41 @@ mod tests {
         assert!(let Err(_) = OpStackElement::try_from(index));
     }
 
+    #[test]
+    fn op_stack_elements_are_iterated_in_stack_order() {
+        for (depth, element) in OpStackElement::iter().enumerate() {
+            let_assert!(Ok(element_again) = OpStackElement::try_from(depth));
+            assert!(element == element_again);
+        }
+    }
+
+    #[test]
+    fn depth_beyond_op_stack_registers_gives_error() {
+        let depth = OpStackElement::COUNT;
+        let_assert!(Err(err) = OpStackElement::try_from(depth));
+        assert!(OpStackElementError::IndexOutOfBounds(16) == err);
+        assert!(let Err(_) = OpStackElement::try_from(usize::MAX));
+    }
+
+    #[test]
+    fn stack_indices_of_registers_are_labeled_with_register_names() {
+        let labels = (0..OpStackElement::COUNT)
+            .map(OpStackElement::label_for_stack_index)
+            .collect_vec();
+        let expected_labels = OpStackElement::iter()
+            .map(|element| format!("st{}", element.index()))
+            .collect_vec();
+        assert!(expected_labels == labels);
+        assert!("st0" == OpStackElement::label_for_stack_index(0));
+        assert!("st15" == OpStackElement::label_for_stack_index(15));
+    }
+
+    #[test]
+    fn stack_indices_beyond_registers_are_labeled_with_raw_index() {
+        assert!("16" == OpStackElement::label_for_stack_index(16));
+        assert!("1337" == OpStackElement::label_for_stack_index(1337));
+    }
+
     #[proptest]
     fn out_of_range_number_of_words_gives_error(
         #[strategy(arb())]
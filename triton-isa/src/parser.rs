@@ -1057,6 +1057,18 @@ pub(crate) mod tests {
         .run();
     }
 
+    #[test]
+    fn duplicate_labels_are_reported_at_both_locations() {
+        let input = "foo: push 1\nhalt\nfoo: pop 1\n";
+        let_assert!(Err(err) = parse(input));
+        let err = err.to_string();
+        assert!(2 == err.match_indices("duplicate label").count());
+        assert!(err.contains("line 1"));
+        assert!(err.contains("line 3"));
+
+        assert!(let Err(_) = crate::program::Program::from_code(input));
+    }
+
     #[test]
     fn parse_program_label() {
         TestCase {
@@ -1073,7 +1085,6 @@ pub(crate) mod tests {
         }
         .run();
 
-        // FIXME: Increase coverage of negative tests for duplicate labels.
         NegativeTestCase {
             input: "foo: pop 1 foo: pop 1 call foo",
             expected_error: "duplicate label",
@@ -1082,6 +1093,22 @@ pub(crate) mod tests {
         }
         .run();
 
+        NegativeTestCase {
+            input: "foo: pop 1 foo: pop 1 foo: pop 1 call foo",
+            expected_error: "duplicate label",
+            expected_error_count: 3,
+            message: "every occurrence of a duplicate label is reported",
+        }
+        .run();
+
+        NegativeTestCase {
+            input: "foo: bar: foo: halt",
+            expected_error: "duplicate label",
+            expected_error_count: 2,
+            message: "duplicate labels are detected even if separated by other labels",
+        }
+        .run();
+
         // FIXME: Increase coverage of negative tests for missing labels.
         NegativeTestCase {
             input: "foo: pop 1 call herp call derp",
@@ -1,18 +1,20 @@
 use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
-use std::hash::Hash;
 use std::io::Cursor;
 
 use arbitrary::Arbitrary;
 use get_size2::GetSize;
+use itertools::EitherOrBoth;
 use itertools::Itertools;
 use serde::Deserialize;
 use serde::Serialize;
 use thiserror::Error;
+use twenty_first::error::ParseBFieldElementError;
 use twenty_first::prelude::*;
 
 use crate::instruction::AnInstruction;
@@ -39,7 +41,7 @@ use crate::parser::ParseError;
 #[derive(Debug, Clone, Eq, Serialize, Deserialize, GetSize)]
 pub struct Program {
     pub instructions: Vec<Instruction>,
-    address_to_label: HashMap<u64, String>,
+    address_to_label: BTreeMap<u64, String>,
     debug_information: DebugInformation,
 }
 
@@ -105,7 +107,7 @@ impl BFieldCodec for Program {
 
         Ok(Box::new(Program {
             instructions,
-            address_to_label: HashMap::default(),
+            address_to_label: BTreeMap::default(),
             debug_information: DebugInformation::default(),
         }))
     }
@@ -223,6 +225,10 @@ struct DebugInformation {
 }
 
 impl Program {
+    /// The version of the binary format used by [`to_bytes`](Self::to_bytes) and
+    /// [`from_bytes`](Self::from_bytes).
+    pub const BYTES_FORMAT_VERSION: u8 = 1;
+
     pub fn new(labelled_instructions: &[LabelledInstruction]) -> Self {
         let label_to_address = parser::build_label_to_address_map(labelled_instructions);
         let instructions =
@@ -238,7 +244,7 @@ impl Program {
         }
     }
 
-    fn flip_map<Key, Value: Eq + Hash>(map: HashMap<Key, Value>) -> HashMap<Value, Key> {
+    fn flip_map<Key, Value: Ord>(map: HashMap<Key, Value>) -> BTreeMap<Value, Key> {
         map.into_iter().map(|(key, value)| (value, key)).collect()
     }
 
@@ -275,7 +281,13 @@ impl Program {
     }
 
     /// Create a `Program` by parsing source code.
+    ///
+    /// A leading byte order mark is ignored. Lines may end in `\n` or `\r\n`, and the last
+    /// line does not need a line ending.
     pub fn from_code(code: &str) -> Result<Self, ParseError> {
+        const BYTE_ORDER_MARK: char = '\u{feff}';
+        let code = code.strip_prefix(BYTE_ORDER_MARK).unwrap_or(code);
+
         parser::parse(code)
             .map(|tokens| parser::to_labelled_instructions(&tokens))
             .map(|instructions| Program::new(&instructions))
@@ -292,7 +304,7 @@ impl Program {
         let mut instruction_stream = instructions_with_labels.into_iter();
         while let Some(instruction) = instruction_stream.next() {
             let instruction_size = instruction.size() as u64;
-            if call_targets.contains(&address) {
+            if call_targets.contains(&address) || self.address_to_label.contains_key(&address) {
                 let label = self.label_for_address(address);
                 let label = LabelledInstruction::Label(label);
                 labelled_instructions.push(label);
@@ -317,8 +329,7 @@ impl Program {
         let leftover_labels = self
             .address_to_label
             .iter()
-            .filter(|(&labels_address, _)| labels_address >= address)
-            .sorted();
+            .filter(|(&labels_address, _)| labels_address >= address);
         for (_, label) in leftover_labels {
             labelled_instructions.push(LabelledInstruction::Label(label.clone()));
         }
@@ -326,6 +337,16 @@ impl Program {
         labelled_instructions
     }
 
+    /// The program in Triton assembly. Equivalent to the program's [`Display`]
+    /// implementation.
+    ///
+    /// [Parsing](Self::from_code) the disassembly gives back the same program,
+    /// including labels, breakpoints, and other debug information. Call targets
+    /// without a label are given a [substitute label](Self::label_for_address).
+    pub fn disassemble(&self) -> String {
+        self.to_string()
+    }
+
     fn call_targets(&self) -> HashSet<u64> {
         self.instructions
             .iter()
@@ -345,6 +366,26 @@ impl Program {
             .unwrap_or_default()
     }
 
+    /// Toggle the breakpoint at the given address without re-parsing the program.
+    /// Returns whether there is a breakpoint at the address after toggling, or `None`
+    /// if the address does not hold the start of an instruction, for example, because
+    /// it holds an instruction's argument. In that case, the program is unchanged.
+    pub fn toggle_breakpoint(&mut self, address: u64) -> Option<bool> {
+        let start = usize::try_from(address).ok()?;
+        let size = self.instructions.get(start)?.size();
+        if self.is_instruction_argument(address) {
+            return None;
+        }
+
+        let is_breakpoint = !self.is_breakpoint(address);
+        let breakpoints = &mut self.debug_information.breakpoints;
+        if breakpoints.len() < self.instructions.len() {
+            breakpoints.resize(self.instructions.len(), false);
+        }
+        breakpoints[start..start + size].fill(is_breakpoint);
+        Some(is_breakpoint)
+    }
+
     pub fn type_hints_at(&self, address: u64) -> Vec<TypeHint> {
         self.debug_information
             .type_hints
@@ -379,6 +420,407 @@ impl Program {
             .collect()
     }
 
+    /// Encode the program in a compact binary format, for example, for embedding
+    /// compiled programs in a host application. Use [`from_bytes`](Self::from_bytes)
+    /// to decode.
+    ///
+    /// The encoding starts with a version byte, followed by the instructions. Each
+    /// instruction is encoded as its one-byte opcode, followed by its argument (if
+    /// any) as 8 little-endian bytes. Labels, type hints, and other annotations are
+    /// not executable and thus not encoded.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![Self::BYTES_FORMAT_VERSION];
+        for instruction in self.clone() {
+            let opcode = u8::try_from(instruction.opcode()).expect("opcodes should fit in u8");
+            bytes.push(opcode);
+            if let Some(arg) = instruction.arg() {
+                bytes.extend(<[u8; BFieldElement::BYTES]>::from(arg));
+            }
+        }
+        bytes
+    }
+
+    /// Decode a program from the binary format produced by
+    /// [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProgramBytesDecodingError> {
+        let Some((&version, mut remaining)) = bytes.split_first() else {
+            return Err(ProgramBytesDecodingError::MissingVersion);
+        };
+        if version != Self::BYTES_FORMAT_VERSION {
+            return Err(ProgramBytesDecodingError::UnsupportedVersion(version));
+        }
+
+        let mut instructions = vec![];
+        while let Some((&opcode, rest)) = remaining.split_first() {
+            let offset = bytes.len() - remaining.len();
+            let mut instruction = Instruction::try_from(u32::from(opcode))
+                .map_err(|err| ProgramBytesDecodingError::InvalidInstruction(offset, err))?;
+            remaining = rest;
+
+            if instruction.arg().is_some() {
+                let Some((arg, rest)) = remaining.split_at_checked(BFieldElement::BYTES) else {
+                    return Err(ProgramBytesDecodingError::TruncatedArgument(
+                        offset,
+                        instruction,
+                    ));
+                };
+                let arg = BFieldElement::try_from(arg)
+                    .map_err(|err| ProgramBytesDecodingError::InvalidArgument(offset, err))?;
+                instruction = instruction
+                    .change_arg(arg)
+                    .map_err(|err| ProgramBytesDecodingError::InvalidInstruction(offset, err))?;
+                remaining = rest;
+            }
+
+            instructions.extend(vec![instruction; instruction.size()]);
+        }
+
+        Ok(Program {
+            instructions,
+            address_to_label: BTreeMap::default(),
+            debug_information: DebugInformation::default(),
+        })
+    }
+
+    /// Whether the given address holds the argument of some instruction, as opposed
+    /// to the start of an instruction. Addresses outside the program are not arguments.
+    pub fn is_instruction_argument(&self, address: u64) -> bool {
+        let Ok(address) = usize::try_from(address) else {
+            return false;
+        };
+        if address >= self.instructions.len() {
+            return false;
+        }
+
+        let mut instruction_start = 0;
+        while instruction_start < address {
+            instruction_start += self.instructions[instruction_start].size();
+        }
+        instruction_start != address
+    }
+
+    /// For every address of the program, whether an instruction starts there, as
+    /// opposed to the address holding an [argument](Self::is_instruction_argument).
+    pub fn instruction_starts(&self) -> Vec<bool> {
+        let mut instruction_starts = vec![false; self.instructions.len()];
+        let mut address = 0;
+        while let Some(instruction) = self.instructions.get(address) {
+            instruction_starts[address] = true;
+            address += instruction.size();
+        }
+        instruction_starts
+    }
+
+    /// An upper bound on the depth of the jump stack when running this program, _i.e._, on
+    /// the nesting depth of [`call`](Instruction::Call)s, if it can be determined
+    /// statically. Helps to predict the height of the Jump Stack Table.
+    ///
+    /// Returns `None` if the call graph contains a cycle, for example, if some subroutine
+    /// [`call`](Instruction::Call)s itself. Loops through [`recurse`](Instruction::Recurse)
+    /// or [`recurse_or_return`](Instruction::RecurseOrReturn) do not grow the jump stack
+    /// and are therefore permitted.
+    ///
+    /// The bound is conservative: every `call` that is reachable within a subroutine is
+    /// assumed to be executed, even if the program's data flow prevents this.
+    pub fn max_jump_stack_depth(&self) -> Option<usize> {
+        let mut max_depths = HashMap::new();
+        self.max_call_depth_from(0, &mut max_depths, &mut HashSet::new())
+    }
+
+    fn max_call_depth_from(
+        &self,
+        entry_point: u64,
+        max_depths: &mut HashMap<u64, usize>,
+        active_entry_points: &mut HashSet<u64>,
+    ) -> Option<usize> {
+        if let Some(&max_depth) = max_depths.get(&entry_point) {
+            return Some(max_depth);
+        }
+        if !active_entry_points.insert(entry_point) {
+            return None;
+        }
+
+        let mut max_depth = 0;
+        for callee in self.callees_reachable_from(entry_point) {
+            let callee_depth = self.max_call_depth_from(callee, max_depths, active_entry_points)?;
+            max_depth = max_depth.max(callee_depth + 1);
+        }
+
+        active_entry_points.remove(&entry_point);
+        max_depths.insert(entry_point, max_depth);
+        Some(max_depth)
+    }
+
+    /// The targets of all [`call`](Instruction::Call)s that are reachable from the given
+    /// entry point without leaving the subroutine starting there.
+    fn callees_reachable_from(&self, entry_point: u64) -> HashSet<u64> {
+        let instruction_at = |address: u64| {
+            let address = usize::try_from(address).ok()?;
+            self.instructions.get(address).copied()
+        };
+
+        let mut callees = HashSet::new();
+        let mut visited = HashSet::new();
+        let mut to_visit = vec![entry_point];
+        while let Some(address) = to_visit.pop() {
+            let Some(instruction) = instruction_at(address) else {
+                continue;
+            };
+            if !visited.insert(address) {
+                continue;
+            }
+
+            let next_address = address + instruction.size() as u64;
+            match instruction {
+                Instruction::Halt
+                | Instruction::Return
+                | Instruction::Recurse
+                | Instruction::RecurseOrReturn => (),
+                Instruction::Call(callee) => {
+                    callees.insert(callee.value());
+                    to_visit.push(next_address);
+                }
+                Instruction::Skiz => {
+                    to_visit.push(next_address);
+                    if let Some(skipped_instruction) = instruction_at(next_address) {
+                        to_visit.push(next_address + skipped_instruction.size() as u64);
+                    }
+                }
+                _ => to_visit.push(next_address),
+            }
+        }
+
+        callees
+    }
+
+    /// All [`call`](Instruction::Call)s in the program, in order of appearance. Each
+    /// call is given as the address of the `call` instruction and the
+    /// [label](Self::label_for_address) of its target.
+    pub fn calls(&self) -> Vec<(u64, String)> {
+        let mut calls = vec![];
+        let mut address = 0;
+        while let Some(&instruction) = self.instructions.get(address) {
+            if let Instruction::Call(target) = instruction {
+                let target = self.label_for_address(target.value());
+                calls.push((address as u64, target));
+            }
+            address += instruction.size();
+        }
+
+        calls
+    }
+
+    /// Whether the two programs are equal. The instructions are always compared;
+    /// whether labels and breakpoints are compared depends on the given
+    /// [`EqualityOptions`]. Other debug information, like type hints, is ignored.
+    ///
+    /// Note that [`PartialEq`] only compares the instructions, which corresponds to
+    /// [`EqualityOptions::default()`].
+    pub fn semantically_equals(&self, other: &Self, options: EqualityOptions) -> bool {
+        let labels_are_equal = || self.address_to_label == other.address_to_label;
+        let breakpoints_are_equal =
+            || self.debug_information.breakpoints == other.debug_information.breakpoints;
+
+        self.instructions == other.instructions
+            && (!options.compare_labels || labels_are_equal())
+            && (!options.compare_breakpoints || breakpoints_are_equal())
+    }
+
+    /// The instructions that need to be removed from, added to, or changed in this
+    /// program to turn it into the `other` program, in order of their addresses.
+    /// Like [`==`](PartialEq::eq), labels and debug information are ignored. In
+    /// particular, the diff is empty if and only if the two programs are equal.
+    ///
+    /// The diff is minimal in the number of instructions that are
+    /// [removed](InstructionDiff::Removed) or [added](InstructionDiff::Added). A
+    /// removal and an addition at the same position are reported as a
+    /// [change](InstructionDiff::Changed).
+    pub fn diff(&self, other: &Self) -> Vec<InstructionDiff> {
+        let old = self.addressed_instructions();
+        let new = other.addressed_instructions();
+
+        let mut diff = vec![];
+        let (mut i, mut j) = (0, 0);
+        let common_instructions = Self::longest_common_subsequence(&old, &new);
+        for (next_i, next_j) in common_instructions
+            .into_iter()
+            .chain([(old.len(), new.len())])
+        {
+            Self::push_diff_hunk(&mut diff, &old[i..next_i], &new[j..next_j]);
+            (i, j) = (next_i + 1, next_j + 1);
+        }
+
+        diff
+    }
+
+    /// All instructions together with their addresses, skipping argument placeholders.
+    fn addressed_instructions(&self) -> Vec<(u64, Instruction)> {
+        let mut addressed_instructions = vec![];
+        let mut address = 0;
+        while let Some(&instruction) = self.instructions.get(address) {
+            addressed_instructions.push((address as u64, instruction));
+            address += instruction.size();
+        }
+
+        addressed_instructions
+    }
+
+    /// The index pairs of a longest common subsequence of the instructions in `old`
+    /// and `new`, in increasing order.
+    ///
+    /// Uses the linear-space variant of [Myers' algorithm][myers]: for sequences of
+    /// lengths n and m that differ in d instructions, it takes O((n + m)·d) time and
+    /// O(n + m) space.
+    ///
+    /// [myers]: https://doi.org/10.1007/BF01840446
+    fn longest_common_subsequence(
+        old: &[(u64, Instruction)],
+        new: &[(u64, Instruction)],
+    ) -> Vec<(usize, usize)> {
+        let mut common_subsequence = vec![];
+        Self::extend_common_subsequence(&mut common_subsequence, old, new, (0, 0));
+        common_subsequence
+    }
+
+    fn extend_common_subsequence(
+        common_subsequence: &mut Vec<(usize, usize)>,
+        old: &[(u64, Instruction)],
+        new: &[(u64, Instruction)],
+        (old_offset, new_offset): (usize, usize),
+    ) {
+        let is_same = |(a, b): &(&(u64, Instruction), &(u64, Instruction))| a.1 == b.1;
+        let prefix_len = old.iter().zip(new).take_while(is_same).count();
+        let suffix_len = old[prefix_len..]
+            .iter()
+            .rev()
+            .zip(new[prefix_len..].iter().rev())
+            .take_while(is_same)
+            .count();
+
+        let matching = |old_start: usize, new_start: usize, len: usize| {
+            (0..len).map(move |i| (old_offset + old_start + i, new_offset + new_start + i))
+        };
+        common_subsequence.extend(matching(0, 0, prefix_len));
+
+        let old_middle = &old[prefix_len..old.len() - suffix_len];
+        let new_middle = &new[prefix_len..new.len() - suffix_len];
+        if !old_middle.is_empty() && !new_middle.is_empty() {
+            let ((snake_start_i, snake_start_j), (snake_end_i, snake_end_j)) =
+                Self::middle_snake(old_middle, new_middle);
+            let (old_offset, new_offset) = (old_offset + prefix_len, new_offset + prefix_len);
+            Self::extend_common_subsequence(
+                common_subsequence,
+                &old_middle[..snake_start_i],
+                &new_middle[..snake_start_j],
+                (old_offset, new_offset),
+            );
+            let snake = (snake_start_i..snake_end_i).zip(snake_start_j..snake_end_j);
+            common_subsequence.extend(snake.map(|(i, j)| (old_offset + i, new_offset + j)));
+            Self::extend_common_subsequence(
+                common_subsequence,
+                &old_middle[snake_end_i..],
+                &new_middle[snake_end_j..],
+                (old_offset + snake_end_i, new_offset + snake_end_j),
+            );
+        }
+
+        let old_suffix_start = old.len() - suffix_len;
+        let new_suffix_start = new.len() - suffix_len;
+        common_subsequence.extend(matching(old_suffix_start, new_suffix_start, suffix_len));
+    }
+
+    /// The start and end of the _middle snake_ of a shortest edit script turning `old`
+    /// into `new`: a (possibly empty) run of common instructions such that the edits
+    /// before and after it are each at most half of all edits. Found by searching for
+    /// shortest edit scripts from both ends simultaneously until they overlap.
+    fn middle_snake(
+        old: &[(u64, Instruction)],
+        new: &[(u64, Instruction)],
+    ) -> ((usize, usize), (usize, usize)) {
+        let (n, m) = (old.len() as isize, new.len() as isize);
+        let delta = n - m;
+        let max_num_edits = (n + m + 1) / 2;
+
+        // For every diagonal k, the furthest index into `old` reached with the current
+        // number of edits, counted from the start in `forward` and from the end in
+        // `backward`. Index into `new` is index into `old` minus k.
+        let diagonal_offset = max_num_edits + 1;
+        let idx = |diagonal: isize| (diagonal + diagonal_offset) as usize;
+        let mut forward = vec![0; idx(diagonal_offset) + 1];
+        let mut backward = forward.clone();
+        let position = |i: isize, j: isize| (i as usize, j as usize);
+
+        for d in 0..=max_num_edits {
+            for k in (-d..=d).step_by(2) {
+                let mut i = if k == -d || (k != d && forward[idx(k - 1)] < forward[idx(k + 1)]) {
+                    forward[idx(k + 1)]
+                } else {
+                    forward[idx(k - 1)] + 1
+                };
+                let snake_start = position(i, i - k);
+                while i < n && i - k < m && old[i as usize].1 == new[(i - k) as usize].1 {
+                    i += 1;
+                }
+                forward[idx(k)] = i;
+
+                let backward_diagonal = delta - k;
+                let may_overlap = delta % 2 != 0 && backward_diagonal.abs() < d;
+                if may_overlap && i + backward[idx(backward_diagonal)] >= n {
+                    return (snake_start, position(i, i - k));
+                }
+            }
+
+            for k in (-d..=d).step_by(2) {
+                let mut i = if k == -d || (k != d && backward[idx(k - 1)] < backward[idx(k + 1)]) {
+                    backward[idx(k + 1)]
+                } else {
+                    backward[idx(k - 1)] + 1
+                };
+                let snake_end = position(n - i, m - (i - k));
+                while i < n
+                    && i - k < m
+                    && old[(n - 1 - i) as usize].1 == new[(m - 1 - (i - k)) as usize].1
+                {
+                    i += 1;
+                }
+                backward[idx(k)] = i;
+
+                let forward_diagonal = delta - k;
+                let may_overlap = delta % 2 == 0 && forward_diagonal.abs() <= d;
+                if may_overlap && forward[idx(forward_diagonal)] + i >= n {
+                    return (position(n - i, m - (i - k)), snake_end);
+                }
+            }
+        }
+
+        unreachable!("the search from both ends must overlap after at most n + m edits")
+    }
+
+    fn push_diff_hunk(
+        diff: &mut Vec<InstructionDiff>,
+        removed: &[(u64, Instruction)],
+        added: &[(u64, Instruction)],
+    ) {
+        let hunk =
+            removed
+                .iter()
+                .zip_longest(added)
+                .map(|removed_or_added| match removed_or_added {
+                    EitherOrBoth::Both(&(address, old), &(_, new)) => {
+                        InstructionDiff::Changed { address, old, new }
+                    }
+                    EitherOrBoth::Left(&(address, instruction)) => InstructionDiff::Removed {
+                        address,
+                        instruction,
+                    },
+                    EitherOrBoth::Right(&(address, instruction)) => InstructionDiff::Added {
+                        address,
+                        instruction,
+                    },
+                });
+        diff.extend(hunk);
+    }
+
     /// The total length of the program as `BFieldElement`s. Double-word instructions contribute
     /// two `BFieldElement`s.
     pub fn len_bwords(&self) -> usize {
@@ -391,6 +833,10 @@ impl Program {
 
     /// Produces the program's canonical hash digest. Uses [`Tip5`], the
     /// canonical hash function for Triton VM.
+    ///
+    /// This is the digest a STARK proof commits to. Only the executable encoding
+    /// is hashed, so label names, type hints, and other annotations do not
+    /// influence it.
     pub fn hash(&self) -> Digest {
         // not encoded using `BFieldCodec` because that would prepend the length
         Tip5::hash_varlen(&self.to_bwords())
@@ -405,6 +851,70 @@ impl Program {
             .cloned()
             .unwrap_or_else(|| format!("address_{address}"))
     }
+
+    /// The label for the given address or, if there is none, the nearest preceding label
+    /// together with the offset from it, like `my_function+3`. If no label precedes the
+    /// address, falls back to the substitute of [`label_for_address`][label].
+    ///
+    /// Unlike [`label_for_address`][label], the returned string is not necessarily
+    /// unique and might not parse as a label. It is intended for human consumption, for
+    /// example when displaying a call stack.
+    ///
+    /// [label]: Self::label_for_address
+    pub fn nearest_label_for_address(&self, address: u64) -> String {
+        match self.address_to_label.range(..=address).next_back() {
+            Some((&label_address, label)) if label_address == address => label.clone(),
+            Some((&label_address, label)) => format!("{label}+{}", address - label_address),
+            None => self.label_for_address(address),
+        }
+    }
+
+    /// The address of the given label, if the program has such a label. The inverse of
+    /// [`label_for_address`](Self::label_for_address), except that substitute labels
+    /// are not recognized.
+    pub fn address_for_label(&self, label: &str) -> Option<u64> {
+        self.address_to_label
+            .iter()
+            .find(|(_, l)| *l == label)
+            .map(|(&address, _)| address)
+    }
+}
+
+/// Which parts of a [`Program`] to consider in [`Program::semantically_equals`].
+/// By default, only the instructions are compared.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Arbitrary)]
+pub struct EqualityOptions {
+    /// Programs with labels of different names or at different addresses are unequal.
+    pub compare_labels: bool,
+
+    /// Programs with breakpoints at different addresses are unequal.
+    pub compare_breakpoints: bool,
+}
+
+/// A single difference between two [`Program`]s, as reported by [`Program::diff`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum InstructionDiff {
+    /// The instruction at the given address of the original program is absent in
+    /// the other program.
+    Removed {
+        address: u64,
+        instruction: Instruction,
+    },
+
+    /// The instruction at the given address of the other program is absent in the
+    /// original program.
+    Added {
+        address: u64,
+        instruction: Instruction,
+    },
+
+    /// The instruction at the given address of the original program is replaced by
+    /// a different instruction in the other program.
+    Changed {
+        address: u64,
+        old: Instruction,
+        new: Instruction,
+    },
 }
 
 #[non_exhaustive]
@@ -429,6 +939,27 @@ pub enum ProgramDecodingError {
     MissingArgument(usize, Instruction),
 }
 
+/// An error that can occur when decoding a [`Program`] from
+/// [bytes](Program::from_bytes).
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum ProgramBytesDecodingError {
+    #[error("bytes to decode are empty and do not contain a format version")]
+    MissingVersion,
+
+    #[error("unsupported format version {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("invalid instruction at byte offset {0}: {1}")]
+    InvalidInstruction(usize, InstructionError),
+
+    #[error("argument of instruction `{name}` at byte offset {0} is truncated", name = .1.name())]
+    TruncatedArgument(usize, Instruction),
+
+    #[error("invalid argument for instruction at byte offset {0}: {1}")]
+    InvalidArgument(usize, ParseBFieldElementError),
+}
+
 #[cfg(test)]
 mod tests {
     use assert2::assert;
@@ -439,6 +970,9 @@ mod tests {
     use rand::Rng;
     use test_strategy::proptest;
 
+    use crate::instruction::ALL_INSTRUCTIONS;
+    use crate::op_stack::NumberOfWords;
+    use crate::op_stack::OpStackElement;
     use crate::triton_program;
 
     use super::*;
@@ -505,12 +1039,433 @@ mod tests {
         assert!(expected_digest == digest);
     }
 
+    #[test]
+    fn label_names_do_not_influence_program_digest() {
+        let program = triton_program!(call foo halt foo: push 1 bar: pop 1 return);
+        let renamed = triton_program!(call baz halt baz: push 1 qux: pop 1 return);
+        assert!(program.hash() == renamed.hash());
+
+        let different = triton_program!(call foo halt foo: push 2 bar: pop 1 return);
+        assert!(program.hash() != different.hash());
+    }
+
+    #[test]
+    fn arguments_of_instructions_are_identified() {
+        let program = triton_program!(push 1 push 1 pop 1 halt);
+        let argument_addresses = (0..10)
+            .filter(|&address| program.is_instruction_argument(address))
+            .collect_vec();
+        assert!(vec![1, 3, 5] == argument_addresses);
+    }
+
+    #[proptest]
+    fn instruction_starts_are_exactly_the_non_arguments(#[strategy(arb())] program: Program) {
+        let instruction_starts = program.instruction_starts();
+        prop_assert_eq!(program.instructions.len(), instruction_starts.len());
+        for (address, is_instruction_start) in (0..).zip(instruction_starts) {
+            prop_assert_ne!(
+                is_instruction_start,
+                program.is_instruction_argument(address)
+            );
+        }
+    }
+
+    #[test]
+    fn max_jump_stack_depth_of_program_without_calls_is_zero() {
+        let program = triton_program!(push 1 push 2 add pop 1 halt);
+        assert!(Some(0) == program.max_jump_stack_depth());
+    }
+
+    #[test]
+    fn max_jump_stack_depth_of_flat_call_chain_is_length_of_chain() {
+        let program = triton_program! {
+            call a call c halt
+            a: call b return
+            b: skiz call c return
+            c: return
+        };
+        assert!(Some(3) == program.max_jump_stack_depth());
+    }
+
+    #[test]
+    fn loops_through_recurse_do_not_increase_max_jump_stack_depth() {
+        let program = triton_program! {
+            push 3 call loop halt
+            loop: dup 0 push 0 eq skiz return push -1 add recurse
+        };
+        assert!(Some(1) == program.max_jump_stack_depth());
+    }
+
+    #[test]
+    fn max_jump_stack_depth_of_recursive_program_is_unknown() {
+        let program = triton_program! {
+            call a halt
+            a: call b return
+            b: skiz call a return
+        };
+        assert!(None == program.max_jump_stack_depth());
+    }
+
+    #[test]
+    fn calls_are_listed_with_their_sites_and_target_labels() {
+        let program = triton_program! {
+            call foo push 0 call bar call foo halt
+            foo: call bar return
+            bar: return
+        };
+        let expected_calls = [(0, "foo"), (4, "bar"), (6, "foo"), (9, "bar")];
+        let expected_calls = expected_calls.map(|(address, label)| (address, label.to_string()));
+        assert!(expected_calls.to_vec() == program.calls());
+    }
+
+    #[test]
+    fn calls_to_unlabelled_addresses_use_substitute_labels() {
+        // decoding a program drops all labels
+        let program = triton_program!(call foo halt foo: return);
+        let program = *Program::decode(&program.encode()).unwrap();
+        assert!(vec![(0, "address_3".to_string())] == program.calls());
+    }
+
+    #[test]
+    fn address_for_label_is_inverse_of_label_for_address() {
+        let program = triton_program!(call foo halt foo: push 1 bar: return);
+        for label in ["foo", "bar"] {
+            let_assert!(Some(address) = program.address_for_label(label));
+            assert!(label == program.label_for_address(address));
+        }
+        assert!(Some(3) == program.address_for_label("foo"));
+    }
+
+    #[test]
+    fn nearest_label_for_labelled_address_is_the_label() {
+        let program = triton_program!(push 1 foo: push 2 pop 2 halt);
+        assert!("foo" == program.nearest_label_for_address(2));
+    }
+
+    #[test]
+    fn nearest_label_for_unlabelled_address_has_offset() {
+        let program = triton_program! {
+            call foo halt
+            foo: push 1 push 2 pop 2
+            bar: push 3 pop 1 return
+        };
+        assert!("foo+2" == program.nearest_label_for_address(5));
+        assert!("foo+4" == program.nearest_label_for_address(7));
+        assert!("bar+2" == program.nearest_label_for_address(11));
+    }
+
+    #[test]
+    fn nearest_label_for_address_before_first_label_is_substitute_label() {
+        let program = triton_program!(push 1 push 2 foo: pop 2 halt);
+        assert!("address_2" == program.nearest_label_for_address(2));
+        assert!(program.label_for_address(2) == program.nearest_label_for_address(2));
+    }
+
+    #[test]
+    fn unknown_or_substitute_labels_have_no_address() {
+        let program = triton_program!(call foo halt foo: return);
+        assert!(None == program.address_for_label("bar"));
+
+        // decoding a program drops all labels
+        let program = *Program::decode(&program.encode()).unwrap();
+        assert!("address_3" == program.label_for_address(3));
+        assert!(None == program.address_for_label("address_3"));
+        assert!(None == program.address_for_label("foo"));
+    }
+
+    #[test]
+    fn program_without_calls_has_no_calls() {
+        let program = triton_program!(push 1 push 2 add pop 1 halt);
+        assert!(program.calls().is_empty());
+    }
+
+    #[test]
+    fn breakpoints_can_be_toggled_at_runtime() {
+        let mut program = triton_program!(push 1 break pop 1 halt);
+        assert!(program.is_breakpoint(2));
+
+        assert!(Some(true) == program.toggle_breakpoint(0));
+        assert!(program.is_breakpoint(0));
+        assert!(program.is_breakpoint(1));
+
+        assert!(Some(false) == program.toggle_breakpoint(2));
+        assert!(!program.is_breakpoint(2));
+
+        let expected = triton_program!(break push 1 pop 1 halt);
+        let options = EqualityOptions {
+            compare_breakpoints: true,
+            ..Default::default()
+        };
+        assert!(expected.semantically_equals(&program, options));
+    }
+
+    #[test]
+    fn toggling_breakpoint_inside_instruction_or_outside_program_is_rejected() {
+        let mut program = triton_program!(push 1 pop 1 halt);
+        let unchanged_program = program.clone();
+
+        assert!(None == program.toggle_breakpoint(1));
+        assert!(None == program.toggle_breakpoint(3));
+        assert!(None == program.toggle_breakpoint(5));
+        assert!(None == program.toggle_breakpoint(u64::MAX));
+
+        let options = EqualityOptions {
+            compare_breakpoints: true,
+            ..Default::default()
+        };
+        assert!(unchanged_program.semantically_equals(&program, options));
+    }
+
+    #[test]
+    fn breakpoints_can_be_toggled_in_decoded_program() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let mut program = *Program::decode(&program.encode()).unwrap();
+        assert!(Some(true) == program.toggle_breakpoint(4));
+        assert!(program.is_breakpoint(4));
+    }
+
+    #[proptest]
+    fn diff_of_program_with_itself_is_empty(#[strategy(arb())] program: Program) {
+        prop_assert!(program.diff(&program).is_empty());
+    }
+
+    #[proptest]
+    fn diff_is_empty_if_and_only_if_programs_are_equal(
+        #[strategy(arb())] program: Program,
+        #[strategy(arb())] other: Program,
+    ) {
+        prop_assert_eq!(program == other, program.diff(&other).is_empty());
+    }
+
+    #[proptest]
+    fn diff_is_minimal(
+        #[strategy(prop::collection::vec(0_usize..4, 0..60))] old_choices: Vec<usize>,
+        #[strategy(prop::collection::vec(0_usize..4, 0..60))] new_choices: Vec<usize>,
+    ) {
+        // Few distinct instructions make long common subsequences likely.
+        let alphabet = ["push 1", "pop 1", "add", "halt"];
+        let program_from = |choices: &[usize]| {
+            let code = choices.iter().map(|&choice| alphabet[choice]).join(" ");
+            Program::from_code(&code).unwrap()
+        };
+        let program = program_from(&old_choices);
+        let other = program_from(&new_choices);
+
+        let (n, m) = (old_choices.len(), new_choices.len());
+        let mut lcs_len = vec![vec![0; m + 1]; n + 1];
+        for (i, j) in (0..n).cartesian_product(0..m) {
+            lcs_len[i + 1][j + 1] = match old_choices[i] == new_choices[j] {
+                true => lcs_len[i][j] + 1,
+                false => lcs_len[i][j + 1].max(lcs_len[i + 1][j]),
+            };
+        }
+        let lcs_len = lcs_len[n][m];
+
+        let diff = program.diff(&other);
+        let num_changed = diff
+            .iter()
+            .filter(|d| matches!(d, InstructionDiff::Changed { .. }))
+            .count();
+        let num_removed = diff
+            .iter()
+            .filter(|d| matches!(d, InstructionDiff::Removed { .. }))
+            .count();
+        let num_added = diff.len() - num_changed - num_removed;
+        prop_assert_eq!(n - lcs_len, num_removed + num_changed);
+        prop_assert_eq!(m - lcs_len, num_added + num_changed);
+
+        // The instructions untouched by the diff must be common to both programs.
+        let touched_addresses = diff
+            .iter()
+            .filter_map(|d| match *d {
+                InstructionDiff::Removed { address, .. } => Some(address),
+                InstructionDiff::Changed { address, .. } => Some(address),
+                InstructionDiff::Added { .. } => None,
+            })
+            .collect::<HashSet<_>>();
+        let mut other_instructions = other.into_iter();
+        for (address, instruction) in program.addressed_instructions() {
+            if !touched_addresses.contains(&address) {
+                prop_assert!(other_instructions.any(|other| other == instruction));
+            }
+        }
+    }
+
+    #[test]
+    fn diff_of_long_programs_with_distant_changes_is_cheap() {
+        let num_instructions = 100_000;
+        let push = |arg: u64| Instruction::Push(bfe!(arg));
+        let program_with_args = |args: &[u64]| Program {
+            instructions: args.iter().flat_map(|&arg| [push(arg); 2]).collect(),
+            address_to_label: BTreeMap::default(),
+            debug_information: DebugInformation::default(),
+        };
+
+        let args = (0..num_instructions).collect_vec();
+        let mut other_args = args.clone();
+        other_args[1] = num_instructions;
+        other_args[num_instructions as usize - 2] = num_instructions + 1;
+
+        let program = program_with_args(&args);
+        let other = program_with_args(&other_args);
+        let expected = vec![
+            InstructionDiff::Changed {
+                address: 2,
+                old: push(1),
+                new: push(num_instructions),
+            },
+            InstructionDiff::Changed {
+                address: 2 * (num_instructions - 2),
+                old: push(num_instructions - 2),
+                new: push(num_instructions + 1),
+            },
+        ];
+        assert!(expected == program.diff(&other));
+    }
+
+    #[test]
+    fn diff_ignores_labels_and_breakpoints() {
+        let program = triton_program!(call foo halt foo: push 1 pop 1 return);
+        let other = triton_program!(call bar halt bar: break push 1 pop 1 return);
+        assert!(program == other);
+        assert!(program.diff(&other).is_empty());
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_instructions_by_address() {
+        let program = triton_program!(push 1 push 2 add dup 0 pop 1 halt);
+        let other = triton_program!(push 1 push 3 add pop 1 write_io 1 halt);
+
+        let push = |arg: u64| Instruction::Push(bfe!(arg));
+        let expected = vec![
+            InstructionDiff::Changed {
+                address: 2,
+                old: push(2),
+                new: push(3),
+            },
+            InstructionDiff::Removed {
+                address: 5,
+                instruction: Instruction::Dup(OpStackElement::ST0),
+            },
+            InstructionDiff::Added {
+                address: 7,
+                instruction: Instruction::WriteIo(NumberOfWords::N1),
+            },
+        ];
+        assert!(expected == program.diff(&other));
+    }
+
+    #[test]
+    fn diff_against_empty_program_removes_all_instructions() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let empty_program = triton_program!();
+
+        let removed_addresses = program
+            .diff(&empty_program)
+            .into_iter()
+            .map(|diff| match diff {
+                InstructionDiff::Removed { address, .. } => address,
+                _ => panic!("unexpected diff: {diff:?}"),
+            })
+            .collect_vec();
+        assert!(vec![0, 2, 4] == removed_addresses);
+        assert!(3 == empty_program.diff(&program).len());
+    }
+
     #[test]
     fn empty_program_is_empty() {
         let program = triton_program!();
         assert!(program.is_empty());
     }
 
+    #[test]
+    fn line_endings_and_byte_order_mark_do_not_change_parsed_program() {
+        let clean_code = "push 1 // comment\ncall foo\nhalt\n\nfoo:\n  break\n  hint bar = stack[0]\n  pop 1\n  return\n";
+        let clean_program = Program::from_code(clean_code).unwrap();
+
+        let crlf_code = clean_code.replace('\n', "\r\n");
+        let no_trailing_newline_code = clean_code.trim_end();
+        let bom_code = format!("\u{feff}{clean_code}");
+        let bom_crlf_code = format!("\u{feff}{}", crlf_code.trim_end());
+
+        for code in [
+            &crlf_code,
+            no_trailing_newline_code,
+            &bom_code,
+            &bom_crlf_code,
+        ] {
+            let program = Program::from_code(code).unwrap();
+            assert!(clean_program == program);
+            assert!(clean_program.labelled_instructions() == program.labelled_instructions());
+        }
+    }
+
+    #[test]
+    fn byte_order_mark_is_only_ignored_at_start_of_program() {
+        let code = "push 1\n\u{feff}halt";
+        let_assert!(Err(_) = Program::from_code(code));
+    }
+
+    #[test]
+    fn programs_differing_only_in_labels_are_equal_unless_labels_are_compared() {
+        let program = triton_program!(call foo halt foo: return);
+        let relabeled_program = triton_program!(call bar halt bar: return);
+        assert!(program == relabeled_program);
+
+        let ignore_labels = EqualityOptions::default();
+        let compare_labels = EqualityOptions {
+            compare_labels: true,
+            ..ignore_labels
+        };
+        assert!(program.semantically_equals(&relabeled_program, ignore_labels));
+        assert!(!program.semantically_equals(&relabeled_program, compare_labels));
+        assert!(program.semantically_equals(&program.clone(), compare_labels));
+    }
+
+    #[test]
+    fn disassembled_program_equals_original_program_unless_labels_are_compared() {
+        let program = triton_program!(call foo halt foo: return);
+        let disassembled = Program::decode(&program.encode()).unwrap();
+
+        let compare_labels = EqualityOptions {
+            compare_labels: true,
+            ..Default::default()
+        };
+        assert!(program.semantically_equals(&disassembled, EqualityOptions::default()));
+        assert!(!program.semantically_equals(&disassembled, compare_labels));
+    }
+
+    #[test]
+    fn programs_differing_only_in_breakpoints_are_equal_unless_breakpoints_are_compared() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let program_with_breakpoint = triton_program!(push 1 break pop 1 halt);
+
+        let ignore_breakpoints = EqualityOptions::default();
+        let compare_breakpoints = EqualityOptions {
+            compare_breakpoints: true,
+            ..ignore_breakpoints
+        };
+        assert!(program.semantically_equals(&program_with_breakpoint, ignore_breakpoints));
+        assert!(!program.semantically_equals(&program_with_breakpoint, compare_breakpoints));
+    }
+
+    #[test]
+    fn programs_with_different_instructions_are_never_semantically_equal() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let other_program = triton_program!(push 2 pop 1 halt);
+
+        let all_options = [false, true]
+            .into_iter()
+            .cartesian_product([false, true])
+            .map(|(compare_labels, compare_breakpoints)| EqualityOptions {
+                compare_labels,
+                compare_breakpoints,
+            });
+        for options in all_options {
+            assert!(!program.semantically_equals(&other_program, options));
+        }
+    }
+
     #[test]
     fn create_program_from_code() {
         let element_3 = thread_rng().gen_range(0_u64..BFieldElement::P);
@@ -583,6 +1538,106 @@ mod tests {
         println!("{program}");
     }
 
+    #[test]
+    fn disassembling_and_parsing_program_with_every_instruction_is_identity() {
+        let mut labelled_instructions = vec![
+            LabelledInstruction::Label("unused_label".to_string()),
+            LabelledInstruction::Breakpoint,
+        ];
+        for instruction in ALL_INSTRUCTIONS {
+            let instruction = instruction.map_call_address(|_| "foo".to_string());
+            labelled_instructions.push(LabelledInstruction::Instruction(instruction));
+        }
+        labelled_instructions.extend([
+            LabelledInstruction::Label("foo".to_string()),
+            LabelledInstruction::Breakpoint,
+            LabelledInstruction::Instruction(AnInstruction::Return),
+        ]);
+        let program = Program::new(&labelled_instructions);
+
+        let disassembly = program.disassemble();
+        let_assert!(Ok(parsed_program) = Program::from_code(&disassembly));
+        let options = EqualityOptions {
+            compare_labels: true,
+            compare_breakpoints: true,
+        };
+        assert!(program.semantically_equals(&parsed_program, options));
+        assert!(disassembly == parsed_program.disassemble());
+    }
+
+    #[test]
+    fn disassembly_puts_labels_and_breakpoints_on_their_own_lines() {
+        let program = triton_program!(foo: break push 1 call bar halt bar: return);
+        let disassembly = program.disassemble();
+        let lines = disassembly.lines().collect_vec();
+        assert!(["foo:", "break", "push 1", "call bar", "halt", "bar:", "return"] == *lines);
+    }
+
+    #[proptest]
+    fn random_program_to_bytes_from_bytes_equivalence(#[strategy(arb())] program: Program) {
+        let bytes = program.to_bytes();
+        let decoded = Program::from_bytes(&bytes)?;
+        prop_assert_eq!(program, decoded);
+    }
+
+    #[test]
+    fn program_bytes_are_compact() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let bytes = program.to_bytes();
+        let instruction_with_argument_len = 1 + BFieldElement::BYTES;
+        assert!(1 + 2 * instruction_with_argument_len + 1 == bytes.len());
+        assert!(Program::BYTES_FORMAT_VERSION == bytes[0]);
+    }
+
+    #[test]
+    fn decoding_program_from_empty_bytes_fails() {
+        let_assert!(Err(err) = Program::from_bytes(&[]));
+        let_assert!(ProgramBytesDecodingError::MissingVersion = err);
+    }
+
+    #[test]
+    fn decoding_program_with_unknown_version_fails() {
+        let mut bytes = triton_program!(halt).to_bytes();
+        bytes[0] = 42;
+        let_assert!(Err(err) = Program::from_bytes(&bytes));
+        let_assert!(ProgramBytesDecodingError::UnsupportedVersion(42) = err);
+    }
+
+    #[test]
+    fn decoding_program_with_unknown_opcode_fails() {
+        let bytes = [Program::BYTES_FORMAT_VERSION, 255];
+        let_assert!(Err(err) = Program::from_bytes(&bytes));
+        let_assert!(ProgramBytesDecodingError::InvalidInstruction(1, inner_err) = err);
+        let_assert!(InstructionError::InvalidOpcode(255) = inner_err);
+    }
+
+    #[test]
+    fn decoding_program_with_truncated_argument_fails() {
+        let mut bytes = triton_program!(halt push 42).to_bytes();
+        bytes.pop();
+        let_assert!(Err(err) = Program::from_bytes(&bytes));
+        let_assert!(ProgramBytesDecodingError::TruncatedArgument(2, instruction) = err);
+        assert!(Instruction::Push(bfe!(0)).opcode() == instruction.opcode());
+    }
+
+    #[test]
+    fn decoding_program_with_non_canonical_argument_fails() {
+        let mut bytes = vec![Program::BYTES_FORMAT_VERSION];
+        bytes.push(u8::try_from(Instruction::Push(bfe!(0)).opcode()).unwrap());
+        bytes.extend(u64::MAX.to_le_bytes());
+        let_assert!(Err(err) = Program::from_bytes(&bytes));
+        let_assert!(ProgramBytesDecodingError::InvalidArgument(1, _) = err);
+    }
+
+    #[test]
+    fn decoding_program_with_illegal_argument_fails() {
+        let mut bytes = vec![Program::BYTES_FORMAT_VERSION];
+        bytes.push(u8::try_from(Instruction::Pop(NumberOfWords::N1).opcode()).unwrap());
+        bytes.extend(0_u64.to_le_bytes());
+        let_assert!(Err(err) = Program::from_bytes(&bytes));
+        let_assert!(ProgramBytesDecodingError::InvalidInstruction(1, _) = err);
+    }
+
     #[proptest]
     fn printed_program_can_be_parsed_again(#[strategy(arb())] program: Program) {
         parser::parse(&program.to_string())?;
@@ -0,0 +1,76 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use itertools::Itertools;
+use ndarray::prelude::*;
+use rand::prelude::StdRng;
+use rand::Rng;
+use rand_core::SeedableRng;
+use strum::EnumCount;
+use twenty_first::prelude::*;
+
+use triton_vm::air::table_column::MasterMainColumn;
+use triton_vm::air::table_column::OpStackMainColumn;
+use triton_vm::config::overwrite_parallelism_threshold_to;
+use triton_vm::table::op_stack::fill_with_sorted_rows;
+
+criterion_main!(benches);
+criterion_group!(
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = op_stack_table_fill<{1 << 8}>,
+              op_stack_table_fill<{1 << 10}>,
+              op_stack_table_fill<{1 << 12}>,
+              op_stack_table_fill<{1 << 14}>,
+              op_stack_table_fill<{1 << 20}>,
+);
+
+fn op_stack_table_fill<const NUM_ROWS: usize>(c: &mut Criterion) {
+    let underflow_trace = underflow_trace(NUM_ROWS);
+    let mut op_stack_table = Array2::zeros(underflow_trace.raw_dim());
+
+    let mut group = c.benchmark_group("op_stack_table_fill");
+    group.bench_function(BenchmarkId::new("serial", NUM_ROWS), |b| {
+        b.iter(|| fill_serially(underflow_trace.view(), op_stack_table.view_mut()))
+    });
+    overwrite_parallelism_threshold_to(usize::MAX);
+    group.bench_function(BenchmarkId::new("sorted_keys_serial", NUM_ROWS), |b| {
+        b.iter(|| fill_with_sorted_rows(underflow_trace.view(), op_stack_table.view_mut()))
+    });
+    overwrite_parallelism_threshold_to(0);
+    group.bench_function(BenchmarkId::new("sorted_keys_parallel", NUM_ROWS), |b| {
+        b.iter(|| fill_with_sorted_rows(underflow_trace.view(), op_stack_table.view_mut()))
+    });
+    group.finish();
+}
+
+/// An op stack underflow trace with one row per clock cycle and random stack
+/// pointers.
+fn underflow_trace(num_rows: usize) -> Array2<BFieldElement> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut underflow_trace = Array2::zeros((num_rows, OpStackMainColumn::COUNT));
+    for (clk, mut row) in underflow_trace.rows_mut().into_iter().enumerate() {
+        let stack_pointer = rng.gen_range(16..1 << 10);
+        row[OpStackMainColumn::StackPointer.main_index()] = bfe!(stack_pointer);
+        row[OpStackMainColumn::CLK.main_index()] = bfe!(clk);
+    }
+
+    underflow_trace
+}
+
+/// The serial approach [`fill_with_sorted_rows`] replaces.
+fn fill_serially(
+    underflow_trace: ArrayView2<BFieldElement>,
+    mut op_stack_table: ArrayViewMut2<BFieldElement>,
+) {
+    let key = |row: &ArrayView1<BFieldElement>| {
+        let stack_pointer = row[OpStackMainColumn::StackPointer.main_index()].value();
+        let clk = row[OpStackMainColumn::CLK.main_index()].value();
+        (stack_pointer, clk)
+    };
+    let sorted_rows = underflow_trace.rows().into_iter().sorted_by_key(key);
+    for (row_index, row) in sorted_rows.enumerate() {
+        op_stack_table.row_mut(row_index).assign(&row);
+    }
+}
@@ -0,0 +1,46 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use triton_vm::prelude::*;
+
+const NUM_PROOFS: u64 = 8;
+
+/// cargo criterion --bench verify_batch
+fn verify_batch(criterion: &mut Criterion) {
+    let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+    let stark = Stark::default();
+    let claims_and_proofs = (0..NUM_PROOFS)
+        .map(|input| {
+            let claim = Claim::about_program(&program).with_input(bfe_vec![input]);
+            let public_input = claim.input.clone().into();
+            let (aet, output) =
+                VM::trace_execution(program.clone(), public_input, [].into()).unwrap();
+            let claim = claim.with_output(output);
+            let proof = stark.prove(&claim, &aet).unwrap();
+            (claim, proof)
+        })
+        .collect::<Vec<_>>();
+
+    let mut group = criterion.benchmark_group("verify_batch");
+    group.bench_function(BenchmarkId::new("loop", NUM_PROOFS), |bencher| {
+        bencher.iter(|| {
+            claims_and_proofs
+                .iter()
+                .map(|(claim, proof)| stark.verify(claim, proof).is_ok())
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function(BenchmarkId::new("batch", NUM_PROOFS), |bencher| {
+        bencher.iter(|| stark.verify_batch(&claims_and_proofs))
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = verify_batch
+}
+
+criterion_main!(benches);
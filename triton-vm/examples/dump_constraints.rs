@@ -0,0 +1,76 @@
+//! # [Triton VM] Example: Dump Constraints
+//!
+//! This example prints the initial, consistency, transition, and terminal constraints of
+//! the Op Stack Table in a human-readable form. Columns and challenges are referred to by
+//! their names. A primed column name, like `CLK'`, refers to the column in the next row.
+//!
+//! This is helpful when auditing the constraint system.
+//!
+//! [Triton VM]: https://triton-vm.org/
+
+use constraint_circuit::ConstraintCircuitBuilder;
+use constraint_circuit::ConstraintCircuitMonad;
+use constraint_circuit::InputIndicator;
+use strum::IntoEnumIterator;
+use triton_vm::air::challenge_id::ChallengeId;
+use triton_vm::air::table::op_stack::OpStackTable;
+use triton_vm::air::table_column::MasterAuxColumn;
+use triton_vm::air::table_column::MasterMainColumn;
+use triton_vm::air::AIR;
+
+type MainColumn = <OpStackTable as AIR>::MainColumn;
+type AuxColumn = <OpStackTable as AIR>::AuxColumn;
+
+fn main() {
+    let single_row_builder = ConstraintCircuitBuilder::new();
+    let dual_row_builder = ConstraintCircuitBuilder::new();
+
+    let initial_constraints = OpStackTable::initial_constraints(&single_row_builder);
+    let consistency_constraints = OpStackTable::consistency_constraints(&single_row_builder);
+    let transition_constraints = OpStackTable::transition_constraints(&dual_row_builder);
+    let terminal_constraints = OpStackTable::terminal_constraints(&single_row_builder);
+
+    println!("# Op Stack Table");
+    print_constraints("Initial", &initial_constraints);
+    print_constraints("Consistency", &consistency_constraints);
+    print_constraints("Transition", &transition_constraints);
+    print_constraints("Terminal", &terminal_constraints);
+}
+
+fn print_constraints<II: InputIndicator>(kind: &str, constraints: &[ConstraintCircuitMonad<II>]) {
+    println!();
+    println!("## {kind} Constraints");
+    println!();
+    for (i, constraint) in constraints.iter().enumerate() {
+        let constraint = constraint
+            .circuit
+            .borrow()
+            .to_string_with_names(&input_name, &challenge_name);
+        println!("{i:>3}: {constraint} = 0");
+    }
+}
+
+fn input_name<II: InputIndicator>(input: II) -> String {
+    let column = input.column();
+    let column_name = if input.is_main_table_column() {
+        MainColumn::iter()
+            .find(|c| c.master_main_index() == column)
+            .map(|c| c.to_string())
+    } else {
+        AuxColumn::iter()
+            .find(|c| c.master_aux_index() == column)
+            .map(|c| c.to_string())
+    };
+    let column_name = column_name.unwrap_or_else(|| format!("column_{column}"));
+
+    match input.is_current_row() {
+        true => column_name,
+        false => format!("{column_name}'"),
+    }
+}
+
+fn challenge_name(challenge: usize) -> String {
+    ChallengeId::iter()
+        .nth(challenge)
+        .map_or_else(|| format!("challenge_{challenge}"), |c| c.to_string())
+}
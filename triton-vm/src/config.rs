@@ -16,11 +16,23 @@
 //! - `TVM_LDE_TRACE`: Set to `cache` to cache the low-degree extended trace.
 //!   Set to `no_cache` to not cache it. If unset (or set to anything else),
 //!   Triton VM will make an automatic decision based on free memory.
+//!
+//! # Parallelism
+//!
+//! Some parts of proof generation only run in parallel if the data they operate
+//! on is large enough, since spawning parallel tasks comes with overhead. The
+//! threshold can be [set](overwrite_parallelism_threshold_to) explicitly or via
+//! the environment variable `TVM_PARALLELISM_THRESHOLD`, which must be a number
+//! of rows. If unset or malformed, [`DEFAULT_PARALLELISM_THRESHOLD`] is used.
 
 use std::cell::RefCell;
 
 use arbitrary::Arbitrary;
 
+/// The number of rows below which operations that can run in parallel run serially
+/// instead. See also [`overwrite_parallelism_threshold_to`].
+pub const DEFAULT_PARALLELISM_THRESHOLD: usize = 1 << 12;
+
 thread_local! {
     pub(crate) static CONFIG: RefCell<Config> = RefCell::new(Config::default());
 }
@@ -41,6 +53,10 @@ struct Config {
     /// [lde]: crate::table::master_table::MasterTable::maybe_low_degree_extend_all_columns
     /// [proving]: crate::stark::Stark::prove
     pub cache_lde_trace_overwrite: Option<CacheDecision>,
+
+    /// The number of rows below which operations that can run in parallel run
+    /// serially instead. Can be accessed via [`parallelism_threshold`].
+    pub parallelism_threshold: usize,
 }
 
 impl Config {
@@ -52,8 +68,14 @@ impl Config {
             _ => None,
         };
 
+        let parallelism_threshold = std::env::var("TVM_PARALLELISM_THRESHOLD")
+            .ok()
+            .and_then(|threshold| threshold.parse().ok())
+            .unwrap_or(DEFAULT_PARALLELISM_THRESHOLD);
+
         Self {
             cache_lde_trace_overwrite,
+            parallelism_threshold,
         }
     }
 }
@@ -85,6 +107,21 @@ pub(crate) fn cache_lde_trace() -> Option<CacheDecision> {
     CONFIG.with_borrow(|config| config.cache_lde_trace_overwrite)
 }
 
+/// Overwrite the number of rows below which operations that can run in parallel
+/// run serially instead. Takes precedence over the environment variable
+/// `TVM_PARALLELISM_THRESHOLD`.
+///
+/// A threshold of 0 means always running in parallel, a threshold of
+/// [`usize::MAX`] means (practically) never running in parallel.
+pub fn overwrite_parallelism_threshold_to(threshold: usize) {
+    CONFIG.with_borrow_mut(|config| config.parallelism_threshold = threshold);
+}
+
+/// Should an operation on the given number of rows run in parallel?
+pub(crate) fn should_run_in_parallel(num_rows: usize) -> bool {
+    CONFIG.with_borrow(|config| num_rows >= config.parallelism_threshold)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::example_programs::FIBONACCI_SEQUENCE;
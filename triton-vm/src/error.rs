@@ -11,11 +11,13 @@ use std::fmt::Formatter;
 
 use thiserror::Error;
 use twenty_first::error::MerkleTreeError;
+use twenty_first::error::ParseBFieldElementError;
 use twenty_first::prelude::*;
 
 use crate::proof_item::ProofItem;
 use crate::proof_item::ProofItemVariant;
 use crate::proof_stream::ProofStream;
+use crate::table::ConstraintType;
 use crate::vm::VMState;
 
 /// Indicates a runtime error that resulted in a crash of Triton VM.
@@ -91,6 +93,22 @@ pub enum FriSetupError {
     ArithmeticDomainError(#[from] ArithmeticDomainError),
 }
 
+/// An error that can occur when customizing the parameters of a [`Stark`].
+///
+/// [`Stark`]: crate::stark::Stark
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
+pub enum StarkParameterError {
+    #[error("the FRI expansion factor must be a power of 2 greater than 1, but is {0}")]
+    UnsupportedFriExpansionFactor(usize),
+
+    #[error("the parameters achieve {achieved} bits of security but must achieve {required}")]
+    InsufficientSecurity { required: usize, achieved: usize },
+
+    #[error("the number of trace randomizers must be at least {min} but is {actual}")]
+    TooFewTraceRandomizers { min: usize, actual: usize },
+}
+
 #[non_exhaustive]
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Error)]
 pub enum FriProvingError {
@@ -122,6 +140,15 @@ pub enum FriValidationError {
     #[error("received codeword of last round does not correspond to its commitment")]
     BadMerkleRootForLastCodeword,
 
+    /// Commonly caused by prover and verifier using different [`Stark`] parameters.
+    ///
+    /// [`Stark`]: crate::stark::Stark
+    #[error(
+        "received codeword of last round has length {actual} but the parameters \
+        require length {expected}"
+    )]
+    IncorrectLastCodewordLength { expected: usize, actual: usize },
+
     #[error(transparent)]
     ProofStreamError(#[from] ProofStreamError),
 
@@ -161,6 +188,18 @@ pub enum ProvingError {
 
     #[error(transparent)]
     VMError(#[from] VMError),
+
+    #[error(
+        "op stack pointer {stack_pointer} in clock cycle {clk} is below the number of \
+        op stack registers"
+    )]
+    OpStackUnderflow { clk: u64, stack_pointer: u64 },
+
+    #[error("proving was cancelled")]
+    Cancelled,
+
+    #[error("internal error during proving: {0}")]
+    Internal(String),
 }
 
 #[non_exhaustive]
@@ -209,6 +248,42 @@ pub enum VerificationError {
     FriValidationError(#[from] FriValidationError),
 }
 
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ProofFileError {
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    DecodingError(#[from] ParseBFieldElementError),
+}
+
+/// The reason a single table's AIR does not hold when checked in isolation, for example,
+/// through [`op_stack::prove_and_verify_in_isolation`](crate::table::op_stack::prove_and_verify_in_isolation).
+#[non_exhaustive]
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum IsolatedTableError {
+    #[error("main table has height {main_height} but auxiliary table has height {aux_height}")]
+    TableHeightMismatch {
+        main_height: usize,
+        aux_height: usize,
+    },
+
+    #[error(
+        "quotient of {constraint_type} constraint {constraint_index} has degree {degree} \
+        but must have degree at most {max_degree}"
+    )]
+    QuotientDegreeTooHigh {
+        constraint_type: ConstraintType,
+        constraint_index: usize,
+        degree: isize,
+        max_degree: isize,
+    },
+
+    #[error(transparent)]
+    ArithmeticDomainError(#[from] ArithmeticDomainError),
+}
+
 #[cfg(test)]
 mod tests {
     use assert2::assert;
@@ -1,3 +1,6 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Display;
 use std::fmt::Formatter;
@@ -5,9 +8,12 @@ use std::fmt::Result as FmtResult;
 use std::ops::Add;
 use std::ops::AddAssign;
 use std::ops::Sub;
+use std::time::Duration;
 
 use air::table::hash::PERMUTATION_TRACE_LENGTH;
 use arbitrary::Arbitrary;
+use isa::instruction::Instruction;
+use itertools::Itertools;
 use twenty_first::prelude::*;
 
 use crate::table::u32::U32TableEntry;
@@ -19,6 +25,9 @@ pub(crate) struct ExecutionTraceProfiler {
     profile: Vec<ProfileLine>,
     table_heights: VMTableHeights,
     u32_table_entries: HashSet<U32TableEntry>,
+    cost_model: CostModel,
+    weighted_cost: u64,
+    wall_time: BTreeMap<String, Duration>,
 }
 
 /// A single line in a [profile report](ExecutionTraceProfile) for profiling
@@ -43,6 +52,32 @@ pub struct ProfileLine {
 pub struct ExecutionTraceProfile {
     pub total: VMTableHeights,
     pub profile: Vec<ProfileLine>,
+
+    /// The sum of the [weights](CostModel) of all executed instructions.
+    pub weighted_cost: u64,
+
+    /// The wall-clock time spent executing each kind of instruction, keyed by the
+    /// instruction's name. Includes the time spent on the instruction's co-processor
+    /// calls. Only recorded by
+    /// [`VM::profile_with_wall_time`](crate::vm::VM::profile_with_wall_time), empty
+    /// otherwise.
+    ///
+    /// The timings are approximate: they include some overhead of the measurement itself
+    /// and are subject to the usual noise, like scheduling and caching effects.
+    pub wall_time: BTreeMap<String, Duration>,
+}
+
+/// Assigns a weight to each [`Instruction`], allowing to model the cost of executing a
+/// program more closely than a plain cycle count does. For example, the weights could
+/// reflect the proving cost of some downstream system.
+///
+/// The weight of an instruction is independent of its argument. By default, every
+/// instruction has weight 1, making the total weighted cost equal to the cycle count.
+///
+/// See also [`VM::profile_with_cost_model`](crate::vm::VM::profile_with_cost_model).
+#[derive(Debug, Default, Clone, Eq, PartialEq, Arbitrary)]
+pub struct CostModel {
+    weights: HashMap<u32, u64>,
 }
 
 /// The heights of various [tables](crate::aet::AlgebraicExecutionTrace) relevant for
@@ -58,12 +93,15 @@ pub struct VMTableHeights {
 }
 
 impl ExecutionTraceProfiler {
-    pub fn new(num_instructions: usize) -> Self {
+    pub fn new(num_instructions: usize, cost_model: CostModel) -> Self {
         Self {
             call_stack: vec![],
             profile: vec![],
             table_heights: VMTableHeights::new(num_instructions),
             u32_table_entries: HashSet::default(),
+            cost_model,
+            weighted_cost: 0,
+            wall_time: BTreeMap::new(),
         }
     }
 
@@ -88,6 +126,15 @@ impl ExecutionTraceProfiler {
         };
     }
 
+    pub fn handle_instruction(&mut self, instruction: Instruction) {
+        self.weighted_cost += self.cost_model.weight(instruction);
+    }
+
+    pub fn handle_wall_time(&mut self, instruction: Instruction, duration: Duration) {
+        let name = instruction.name().to_string();
+        *self.wall_time.entry(name).or_default() += duration;
+    }
+
     pub fn handle_co_processor_calls(&mut self, calls: Vec<CoProcessorCall>) {
         self.table_heights.processor += 1;
         for call in calls {
@@ -115,10 +162,31 @@ impl ExecutionTraceProfiler {
         ExecutionTraceProfile {
             total: self.table_heights,
             profile: self.profile,
+            weighted_cost: self.weighted_cost,
+            wall_time: self.wall_time,
         }
     }
 }
 
+impl CostModel {
+    /// The weight of instructions without an explicitly set weight.
+    pub const DEFAULT_WEIGHT: u64 = 1;
+
+    /// Set the weight of the given instruction. The instruction's argument, if any,
+    /// is ignored.
+    #[must_use]
+    pub fn with_weight(mut self, instruction: Instruction, weight: u64) -> Self {
+        self.weights.insert(instruction.opcode(), weight);
+        self
+    }
+
+    /// The weight of the given instruction. The instruction's argument, if any, is ignored.
+    pub fn weight(&self, instruction: Instruction) -> u64 {
+        let weight = self.weights.get(&instruction.opcode());
+        weight.copied().unwrap_or(Self::DEFAULT_WEIGHT)
+    }
+}
+
 impl VMTableHeights {
     fn new(num_instructions: usize) -> Self {
         let padded_program_len = (num_instructions + 1).next_multiple_of(Tip5::RATE);
@@ -269,6 +337,22 @@ impl Display for ExecutionTraceProfile {
             writeln!(f, "|")?;
         }
 
+        if self.wall_time.is_empty() {
+            return Ok(());
+        }
+
+        let total_wall_time = self.wall_time.values().sum::<Duration>();
+        let wall_times = self.wall_time.iter().sorted_by_key(|&(_, &t)| Reverse(t));
+        let [instruction, wall_time] = ["Instruction", "Wall Time (approx.)"];
+        writeln!(f)?;
+        writeln!(f, "| {instruction:<COL_WIDTH$} | {wall_time:>COL_WIDTH$} |")?;
+        writeln!(f, "|:{dash:-<COL_WIDTH$}-|-{dash:->COL_WIDTH$}:|")?;
+        for (name, &time) in wall_times {
+            let rel = 100.0 * time.as_secs_f64() / total_wall_time.as_secs_f64();
+            let time = format!("{time:.1?} ({rel:>5.1}%)");
+            writeln!(f, "| {name:<COL_WIDTH$} | {time:>COL_WIDTH$} |")?;
+        }
+
         Ok(())
     }
 }
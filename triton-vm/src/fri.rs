@@ -13,6 +13,7 @@ use crate::profiler::profiler;
 use crate::proof_item::FriResponse;
 use crate::proof_item::ProofItem;
 use crate::proof_stream::ProofStream;
+use crate::proof_stream::Transcript;
 
 pub(crate) type SetupResult<T> = Result<T, FriSetupError>;
 pub(crate) type ProverResult<T> = Result<T, FriProvingError>;
@@ -28,8 +29,8 @@ pub struct Fri {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct FriProver<'stream> {
-    proof_stream: &'stream mut ProofStream,
+struct FriProver<'stream, T: Transcript> {
+    proof_stream: &'stream mut ProofStream<T>,
     rounds: Vec<ProverRound>,
     first_round_domain: ArithmeticDomain,
     num_rounds: usize,
@@ -44,7 +45,7 @@ struct ProverRound {
     merkle_tree: MerkleTree,
 }
 
-impl FriProver<'_> {
+impl<T: Transcript> FriProver<'_, T> {
     fn commit(&mut self, codeword: &[XFieldElement]) -> ProverResult<()> {
         self.commit_to_first_round(codeword)?;
         for _ in 0..self.num_rounds {
@@ -191,8 +192,8 @@ impl ProverRound {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct FriVerifier<'stream> {
-    proof_stream: &'stream mut ProofStream,
+struct FriVerifier<'stream, T: Transcript> {
+    proof_stream: &'stream mut ProofStream<T>,
     rounds: Vec<VerifierRound>,
     first_round_domain: ArithmeticDomain,
     last_round_codeword: Vec<XFieldElement>,
@@ -212,7 +213,7 @@ struct VerifierRound {
     folding_challenge: Option<XFieldElement>,
 }
 
-impl FriVerifier<'_> {
+impl<T: Transcript> FriVerifier<'_, T> {
     fn initialize(&mut self) -> VerifierResult<()> {
         let domain = self.first_round_domain;
         let first_round = self.construct_round_with_domain(domain)?;
@@ -226,10 +227,20 @@ impl FriVerifier<'_> {
         }
 
         self.last_round_codeword = self.proof_stream.dequeue()?.try_into_fri_codeword()?;
+        self.assert_last_round_codeword_has_expected_length()?;
         self.last_round_polynomial = self.proof_stream.dequeue()?.try_into_fri_polynomial()?;
         Ok(())
     }
 
+    fn assert_last_round_codeword_has_expected_length(&self) -> VerifierResult<()> {
+        let expected = self.rounds.last().unwrap().domain.length;
+        let actual = self.last_round_codeword.len();
+        match expected == actual {
+            true => Ok(()),
+            false => Err(FriValidationError::IncorrectLastCodewordLength { expected, actual }),
+        }
+    }
+
     fn construct_round_with_domain(
         &mut self,
         domain: ArithmeticDomain,
@@ -522,10 +533,10 @@ impl Fri {
     }
 
     /// Create a FRI proof and return a-indices of revealed elements of round 0.
-    pub fn prove(
+    pub fn prove<T: Transcript>(
         &self,
         codeword: &[XFieldElement],
-        proof_stream: &mut ProofStream,
+        proof_stream: &mut ProofStream<T>,
     ) -> ProverResult<Vec<usize>> {
         let mut prover = self.prover(proof_stream);
 
@@ -541,10 +552,10 @@ impl Fri {
         Ok(prover.first_round_collinearity_check_indices)
     }
 
-    fn prover<'stream>(
+    fn prover<'stream, T: Transcript>(
         &'stream self,
-        proof_stream: &'stream mut ProofStream,
-    ) -> FriProver<'stream> {
+        proof_stream: &'stream mut ProofStream<T>,
+    ) -> FriProver<'stream, T> {
         FriProver {
             proof_stream,
             rounds: vec![],
@@ -557,9 +568,9 @@ impl Fri {
 
     /// Verify low-degreeness of the polynomial on the proof stream.
     /// Returns the indices and revealed elements of the codeword at the top level of the FRI proof.
-    pub fn verify(
+    pub fn verify<T: Transcript>(
         &self,
-        proof_stream: &mut ProofStream,
+        proof_stream: &mut ProofStream<T>,
     ) -> VerifierResult<Vec<(usize, XFieldElement)>> {
         profiler!(start "init");
         let mut verifier = self.verifier(proof_stream);
@@ -577,10 +588,10 @@ impl Fri {
         Ok(verifier.first_round_partially_revealed_codeword())
     }
 
-    fn verifier<'stream>(
+    fn verifier<'stream, T: Transcript>(
         &'stream self,
-        proof_stream: &'stream mut ProofStream,
-    ) -> FriVerifier<'stream> {
+        proof_stream: &'stream mut ProofStream<T>,
+    ) -> FriVerifier<'stream, T> {
         FriVerifier {
             proof_stream,
             rounds: vec![],
@@ -856,10 +867,37 @@ mod tests {
         };
     }
 
+    #[proptest(cases = 50)]
+    fn last_round_codeword_of_wrong_length_results_in_validation_failure(
+        fri: Fri,
+        #[strategy(arbitrary_polynomial())] polynomial: XfePoly,
+    ) {
+        let codeword = fri.domain.evaluate(&polynomial);
+        let mut proof_stream = ProofStream::new();
+        fri.prove(&codeword, &mut proof_stream).unwrap();
+
+        let mut proof_stream = prepare_proof_stream_for_verification(proof_stream);
+        let mut proof_items = proof_stream.items.iter_mut();
+        let last_round_codeword = proof_items.find_map(fri_codeword_filter()).unwrap();
+        let expected = last_round_codeword.len();
+        last_round_codeword.push(xfe!(0));
+
+        let err = fri.verify(&mut proof_stream).unwrap_err();
+        let FriValidationError::IncorrectLastCodewordLength {
+            expected: e,
+            actual,
+        } = err
+        else {
+            return Err(TestCaseError::Fail("validation must fail".into()));
+        };
+        prop_assert_eq!(expected, e);
+        prop_assert_eq!(expected + 1, actual);
+    }
+
     #[must_use]
     fn prepare_proof_stream_for_verification(mut proof_stream: ProofStream) -> ProofStream {
         proof_stream.items_index = 0;
-        proof_stream.sponge = Tip5::init();
+        proof_stream.transcript = Tip5::init();
         proof_stream
     }
 
@@ -312,6 +312,11 @@ mod tests {
         implements_auto_traits::<NonDeterminism>();
         implements_auto_traits::<Program>();
         implements_auto_traits::<PublicInput>();
+        implements_auto_traits::<vm::ParseMode>();
+        implements_auto_traits::<vm::StackDepthOutcome>();
+        implements_auto_traits::<vm::RunOutcome>();
+        implements_auto_traits::<vm::Provenance>();
+        implements_auto_traits::<vm::MissingInput>();
         implements_auto_traits::<Claim>();
         implements_auto_traits::<Proof>();
         implements_auto_traits::<Prover>();
@@ -325,10 +330,13 @@ mod tests {
         implements_auto_traits::<error::ArithmeticDomainError>();
         implements_auto_traits::<error::ProofStreamError>();
         implements_auto_traits::<error::FriSetupError>();
+        implements_auto_traits::<error::StarkParameterError>();
         implements_auto_traits::<error::FriProvingError>();
         implements_auto_traits::<error::FriValidationError>();
         implements_auto_traits::<error::ProvingError>();
         implements_auto_traits::<error::VerificationError>();
+        implements_auto_traits::<error::IsolatedTableError>();
+        implements_auto_traits::<error::ProofFileError>();
 
         // table things
         implements_auto_traits::<challenges::Challenges>();
@@ -339,12 +347,14 @@ mod tests {
         implements_auto_traits::<table::master_table::MasterAuxTable>();
         implements_auto_traits::<table::op_stack::OpStackTableEntry>();
         implements_auto_traits::<table::ram::RamTableCall>();
+        implements_auto_traits::<table::ram::RamInconsistency>();
         implements_auto_traits::<table::u32::U32TableEntry>();
 
         // other
         implements_auto_traits::<aet::AlgebraicExecutionTrace>();
         implements_auto_traits::<aet::TableHeight>();
         implements_auto_traits::<arithmetic_domain::ArithmeticDomain>();
+        implements_auto_traits::<execution_trace_profiler::CostModel>();
         implements_auto_traits::<execution_trace_profiler::ExecutionTraceProfile>();
         implements_auto_traits::<execution_trace_profiler::ProfileLine>();
         implements_auto_traits::<execution_trace_profiler::VMTableHeights>();
@@ -353,11 +363,23 @@ mod tests {
         implements_auto_traits::<memory_layout::MemoryRegion>();
         implements_auto_traits::<memory_layout::StaticTasmConstraintEvaluationMemoryLayout>();
         implements_auto_traits::<profiler::VMPerformanceProfile>();
+        implements_auto_traits::<proof::ProofSizeBreakdown>();
+        implements_auto_traits::<proof::ProofSizeComparison>();
         implements_auto_traits::<proof_item::FriResponse>();
         implements_auto_traits::<proof_item::ProofItem>();
         implements_auto_traits::<proof_stream::ProofStream>();
+        implements_auto_traits::<proof_stream::RecordingTranscript>();
+        implements_auto_traits::<proof_stream::TranscriptEvent>();
+        implements_auto_traits::<stark::CancellationToken>();
+        implements_auto_traits::<stark::ProofSizeEstimate>();
+        implements_auto_traits::<table::ColumnStatistics<air::table_column::OpStackMainColumn>>();
         implements_auto_traits::<TypeHint>();
         implements_auto_traits::<vm::CoProcessorCall>();
+        implements_auto_traits::<vm::CompactTrace>();
+        implements_auto_traits::<vm::TraceRecord>();
+        implements_auto_traits::<vm::InstructionProfile>();
+        implements_auto_traits::<vm::InstructionProfileEntry>();
+        implements_auto_traits::<vm::StateDelta>();
     }
 
     #[proptest]
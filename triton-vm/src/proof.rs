@@ -1,12 +1,25 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::path::Path;
+use std::path::PathBuf;
+
 use arbitrary::Arbitrary;
 use get_size2::GetSize;
 use isa::program::Program;
 use serde::Deserialize;
 use serde::Serialize;
+use twenty_first::error::ParseBFieldElementError;
 use twenty_first::prelude::*;
 
+use crate::error::ProofFileError;
 use crate::error::ProofStreamError;
+use crate::error::ProvingError;
+use crate::proof_item::ProofItem;
 use crate::proof_stream::ProofStream;
+use crate::vm::NonDeterminism;
+use crate::vm::PublicInput;
+use crate::vm::VM;
 
 /// A version tag for the combination of Triton VM's
 /// [instruction set architecture (ISA)][isa] as well as the
@@ -56,6 +69,199 @@ impl Proof {
 
         Ok(1 << log_2_padded_height)
     }
+
+    /// The size of the proof in bytes, broken down by component. See
+    /// [`ProofSizeBreakdown`] for details.
+    pub fn size_breakdown(&self) -> Result<ProofSizeBreakdown, ProofStreamError> {
+        let mut breakdown = ProofSizeBreakdown {
+            total: self.0.len() * BFieldElement::BYTES,
+            ..ProofSizeBreakdown::default()
+        };
+
+        for item in ProofStream::try_from(self)?.items {
+            let num_bytes = item.encode().len() * BFieldElement::BYTES;
+            let component = match item {
+                ProofItem::MerkleRoot(_) => &mut breakdown.merkle_roots,
+                ProofItem::OutOfDomainMainRow(_)
+                | ProofItem::OutOfDomainAuxRow(_)
+                | ProofItem::OutOfDomainQuotientSegments(_) => &mut breakdown.out_of_domain_rows,
+                ProofItem::AuthenticationStructure(_)
+                | ProofItem::MasterMainTableRows(_)
+                | ProofItem::MasterAuxTableRows(_)
+                | ProofItem::QuotientSegmentsElements(_) => &mut breakdown.openings,
+                ProofItem::FriCodeword(_)
+                | ProofItem::FriPolynomial(_)
+                | ProofItem::FriResponse(_) => &mut breakdown.fri,
+                ProofItem::Log2PaddedHeight(_) => &mut breakdown.other,
+            };
+            *component += num_bytes;
+        }
+
+        let num_item_bytes = breakdown.merkle_roots
+            + breakdown.out_of_domain_rows
+            + breakdown.openings
+            + breakdown.fri
+            + breakdown.other;
+        breakdown.other += breakdown.total - num_item_bytes;
+
+        Ok(breakdown)
+    }
+
+    /// Compare the sizes of two proofs, for example ones generated with different
+    /// [STARK parameters](crate::stark::Stark). The comparison's [`Display`]
+    /// implementation renders a table of the two [size breakdowns](Self::size_breakdown).
+    pub fn compare_size(&self, other: &Self) -> Result<ProofSizeComparison, ProofStreamError> {
+        let comparison = ProofSizeComparison {
+            left: self.size_breakdown()?,
+            right: other.size_breakdown()?,
+        };
+        Ok(comparison)
+    }
+
+    /// A canonical byte representation of the proof, consisting of the canonical,
+    /// little-endian encoding of every [`BFieldElement`]. Two proofs are equal if and
+    /// only if their canonical byte representations are equal, which makes this
+    /// representation suitable for diffing and for checking proofs' reproducibility.
+    ///
+    /// Note that proving is randomized in order to achieve zero-knowledge. To generate
+    /// reproducible proofs, [fix the prover's randomness seed][seed].
+    ///
+    /// [seed]: crate::stark::Prover::set_randomness_seed_which_may_break_zero_knowledge
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        self.0
+            .iter()
+            .flat_map(|&element| <[u8; BFieldElement::BYTES]>::from(element))
+            .collect()
+    }
+
+    /// Write the proof to a file in the given directory. The file's name is derived from
+    /// the given claim, see [`Claim::proof_file_name`]. Returns the path of the written
+    /// file.
+    ///
+    /// See also [`load_for_claim`](Self::load_for_claim).
+    pub fn save_for_claim(&self, dir: impl AsRef<Path>, claim: &Claim) -> std::io::Result<PathBuf> {
+        let path = dir.as_ref().join(claim.proof_file_name());
+        std::fs::write(&path, self.to_canonical_bytes())?;
+        Ok(path)
+    }
+
+    /// Read the proof for the given claim from the given directory, as written by
+    /// [`save_for_claim`](Self::save_for_claim). The proof is not verified.
+    pub fn load_for_claim(dir: impl AsRef<Path>, claim: &Claim) -> Result<Self, ProofFileError> {
+        let path = dir.as_ref().join(claim.proof_file_name());
+        let bytes = std::fs::read(path)?;
+        Ok(Self::from_canonical_bytes(&bytes)?)
+    }
+
+    /// The inverse of [`to_canonical_bytes`](Self::to_canonical_bytes).
+    ///
+    /// # Errors
+    ///
+    /// Errors if the number of bytes is not a multiple of [`BFieldElement::BYTES`] or if
+    /// any of the encoded elements is not canonical.
+    pub fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, ParseBFieldElementError> {
+        let chunks = bytes.chunks_exact(BFieldElement::BYTES);
+        let remainder = chunks.remainder();
+        if !remainder.is_empty() {
+            return Err(ParseBFieldElementError::InvalidNumBytes(remainder.len()));
+        }
+
+        let elements = chunks
+            .map(BFieldElement::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(Self(elements))
+    }
+}
+
+/// The size of a [`Proof`] in bytes, broken down by the kind of
+/// [proof item](ProofItem) contributing to it. Sizes refer to the
+/// [canonical byte representation](Proof::to_canonical_bytes) of the proof.
+///
+/// See also [`Proof::size_breakdown`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ProofSizeBreakdown {
+    /// The size of the entire proof; the sum of all other components.
+    pub total: usize,
+
+    /// The commitments to the master tables, the quotient segments, and all FRI
+    /// rounds.
+    pub merkle_roots: usize,
+
+    /// The rows of the master tables and quotient segments that are evaluated outside
+    /// the FRI domain.
+    pub out_of_domain_rows: usize,
+
+    /// The revealed rows of the master tables and quotient segments, including their
+    /// authentication structures.
+    pub openings: usize,
+
+    /// The FRI layers: the last codeword and polynomial as well as the revealed
+    /// codeword elements of all rounds, including their authentication structures.
+    pub fri: usize,
+
+    /// The padded height and the encoding overhead, like length indicators.
+    pub other: usize,
+}
+
+/// Two [size breakdowns](ProofSizeBreakdown), for example of proofs generated with
+/// different [STARK parameters](crate::stark::Stark).
+///
+/// See also [`Proof::compare_size`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ProofSizeComparison {
+    pub left: ProofSizeBreakdown,
+    pub right: ProofSizeBreakdown,
+}
+
+impl ProofSizeBreakdown {
+    fn components(&self) -> [(&'static str, usize); 6] {
+        [
+            ("Merkle roots", self.merkle_roots),
+            ("Out-of-domain rows", self.out_of_domain_rows),
+            ("Openings", self.openings),
+            ("FRI", self.fri),
+            ("Other", self.other),
+            ("Total", self.total),
+        ]
+    }
+}
+
+impl Display for ProofSizeComparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        const LABEL_WIDTH: usize = 20;
+        const COL_WIDTH: usize = 12;
+
+        let [component, left, right, difference] = ["Component", "Left", "Right", "Difference"];
+        write!(f, "| {component:<LABEL_WIDTH$} ")?;
+        write!(f, "| {left:>COL_WIDTH$} ")?;
+        write!(f, "| {right:>COL_WIDTH$} ")?;
+        write!(f, "| {difference:>COL_WIDTH$} ")?;
+        writeln!(f, "|")?;
+
+        let dash = "-";
+        write!(f, "|:{dash:-<LABEL_WIDTH$}-")?;
+        write!(f, "|-{dash:->COL_WIDTH$}:")?;
+        write!(f, "|-{dash:->COL_WIDTH$}:")?;
+        write!(f, "|-{dash:->COL_WIDTH$}:")?;
+        writeln!(f, "|")?;
+
+        let left_components = self.left.components();
+        let right_components = self.right.components();
+        for ((label, left), (_, right)) in left_components.into_iter().zip(right_components) {
+            let difference = if right >= left {
+                format!("+{}", right - left)
+            } else {
+                format!("-{}", left - right)
+            };
+            write!(f, "| {label:<LABEL_WIDTH$} ")?;
+            write!(f, "| {left:>COL_WIDTH$} ")?;
+            write!(f, "| {right:>COL_WIDTH$} ")?;
+            write!(f, "| {difference:>COL_WIDTH$} ")?;
+            writeln!(f, "|")?;
+        }
+
+        Ok(())
+    }
 }
 
 /// Contains the public information of a verifiably correct computation.
@@ -120,20 +326,72 @@ impl Claim {
         self.version = version;
         self
     }
+
+    /// Check that this claim is about the given program and that running the program
+    /// on the claimed input and the given non-determinism produces the claimed output.
+    /// Running the program is considerably cheaper than proving it, which makes this
+    /// check a sensible sanity check before generating a proof.
+    ///
+    /// # Errors
+    ///
+    /// - [`ProvingError::ProgramDigestMismatch`] if the claim is about a different
+    ///   program,
+    /// - [`ProvingError::VMError`] if the program crashes, for example because the
+    ///   claimed input is too short, and
+    /// - [`ProvingError::PublicOutputMismatch`] if the program produces output other
+    ///   than the claimed one.
+    pub fn validate_against_program(
+        &self,
+        program: &Program,
+        non_determinism: NonDeterminism,
+    ) -> Result<(), ProvingError> {
+        if program.hash() != self.program_digest {
+            return Err(ProvingError::ProgramDigestMismatch);
+        }
+        let public_input = PublicInput::new(self.input.clone());
+        let public_output = VM::run(program.clone(), public_input, non_determinism)?;
+        if public_output != self.output {
+            return Err(ProvingError::PublicOutputMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// A canonical name for a file holding a [`Proof`] for this claim. The name consists
+    /// of a prefix of the [program digest][digest], which makes it easy to find all
+    /// proofs about some program, and a prefix of the hash of the entire claim, which
+    /// distinguishes between claims about the same program.
+    ///
+    /// See also [`Proof::save_for_claim`] and [`Proof::load_for_claim`].
+    ///
+    /// [digest]: Self::program_digest
+    pub fn proof_file_name(&self) -> String {
+        const PREFIX_LEN: usize = 16;
+        let program_digest = self.program_digest.to_hex();
+        let claim_digest = Tip5::hash(self).to_hex();
+
+        let program_digest = &program_digest[..PREFIX_LEN];
+        let claim_digest = &claim_digest[..PREFIX_LEN];
+        format!("{program_digest}_{claim_digest}.proof")
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use assert2::assert;
+    use assert2::let_assert;
+    use fs_err as fs;
     use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use rand::prelude::StdRng;
+    use rand::random;
     use rand_core::SeedableRng;
     use test_strategy::proptest;
 
     use crate::prelude::*;
-    use crate::proof_item::ProofItem;
+    use crate::shared_tests::low_security_stark;
+    use crate::shared_tests::DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS;
 
     use super::*;
 
@@ -152,6 +410,167 @@ mod tests {
             .with_input(PublicInput::new(bfe_vec![42]));
     }
 
+    #[test]
+    fn proof_file_names_of_claims_about_same_program_share_prefix() {
+        let claim = Claim::default().with_input(bfe_vec![1]);
+        let other_claim = Claim::default().with_input(bfe_vec![2]);
+
+        let file_name = claim.proof_file_name();
+        let other_file_name = other_claim.proof_file_name();
+        assert!(file_name != other_file_name);
+        assert!(file_name[..16] == other_file_name[..16]);
+        assert!(file_name == claim.clone().proof_file_name());
+    }
+
+    #[test]
+    fn claim_with_matching_program_and_output_is_valid() {
+        let program = triton_program!(read_io 1 divine 1 mul write_io 1 halt);
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![3])
+            .with_output(bfe_vec![15]);
+        let non_determinism = NonDeterminism::new(bfe_vec![5]);
+        assert!(let Ok(()) = claim.validate_against_program(&program, non_determinism));
+    }
+
+    #[test]
+    fn claim_about_other_program_is_invalid() {
+        let program = triton_program!(push 1 assert halt);
+        let other_program = triton_program!(push 2 assert halt);
+        let claim = Claim::about_program(&other_program);
+        let_assert!(Err(err) = claim.validate_against_program(&program, [].into()));
+        assert!(let ProvingError::ProgramDigestMismatch = err);
+    }
+
+    #[test]
+    fn claim_with_too_short_input_is_invalid() {
+        let program = triton_program!(read_io 2 add write_io 1 halt);
+        let claim = Claim::about_program(&program)
+            .with_input(bfe_vec![3])
+            .with_output(bfe_vec![3]);
+        let_assert!(Err(err) = claim.validate_against_program(&program, [].into()));
+        let_assert!(ProvingError::VMError(vm_error) = err);
+        assert!(let isa::error::InstructionError::EmptyPublicInput(_) = vm_error.source);
+    }
+
+    #[proptest(cases = 10)]
+    fn claim_with_incorrect_output_is_invalid(
+        #[strategy(vec(arb(), 0..5))] claimed_output: Vec<BFieldElement>,
+    ) {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let input = bfe_vec![3];
+        prop_assume!(claimed_output != bfe_vec![6]);
+
+        let claim = Claim::about_program(&program)
+            .with_input(input)
+            .with_output(claimed_output);
+        let_assert!(Err(err) = claim.validate_against_program(&program, [].into()));
+        assert!(let ProvingError::PublicOutputMismatch = err);
+    }
+
+    #[test]
+    fn proof_saved_for_claim_can_be_loaded_and_verified() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program).with_input(bfe_vec![3]);
+        let input = claim.input.clone().into();
+        let (aet, output) = VM::trace_execution(program, input, [].into()).unwrap();
+        let claim = claim.with_output(output);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove(&claim, &aet).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("triton-vm-proofs-{}", random::<u64>()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = proof.save_for_claim(&dir, &claim).unwrap();
+        assert!(path.ends_with(claim.proof_file_name()));
+
+        let loaded_proof = Proof::load_for_claim(&dir, &claim).unwrap();
+        assert!(proof == loaded_proof);
+        assert!(let Ok(()) = stark.verify(&claim, &loaded_proof));
+
+        let other_claim = claim.with_input(bfe_vec![4]);
+        let_assert!(Err(ProofFileError::IoError(_)) = Proof::load_for_claim(&dir, &other_claim));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[proptest(cases = 10)]
+    fn size_breakdown_attributes_merkle_roots_and_padded_height(#[strategy(arb())] root: Digest) {
+        let mut proof_stream = ProofStream::new();
+        proof_stream.enqueue(ProofItem::Log2PaddedHeight(8));
+        proof_stream.enqueue(ProofItem::MerkleRoot(root));
+        let proof: Proof = proof_stream.into();
+
+        let_assert!(Ok(breakdown) = proof.size_breakdown());
+        let root_item_size = ProofItem::MerkleRoot(root).encode().len() * BFieldElement::BYTES;
+        prop_assert_eq!(root_item_size, breakdown.merkle_roots);
+        prop_assert_eq!(0, breakdown.out_of_domain_rows);
+        prop_assert_eq!(0, breakdown.openings);
+        prop_assert_eq!(0, breakdown.fri);
+        prop_assert_eq!(breakdown.total - root_item_size, breakdown.other);
+    }
+
+    #[test]
+    fn size_comparison_of_proofs_with_different_expansion_factors() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program).with_input(bfe_vec![3]);
+        let input = claim.input.clone().into();
+        let (aet, output) = VM::trace_execution(program, input, [].into()).unwrap();
+        let claim = claim.with_output(output);
+
+        let prove = |log2_expansion_factor| {
+            let stark = low_security_stark(log2_expansion_factor);
+            stark.prove(&claim, &aet).unwrap()
+        };
+        let proof = prove(1);
+        let other_proof = prove(2);
+
+        let_assert!(Ok(comparison) = proof.compare_size(&other_proof));
+        for breakdown in [comparison.left, comparison.right] {
+            let [components @ .., (_, total)] = breakdown.components();
+            assert!(total == components.iter().map(|&(_, size)| size).sum::<usize>());
+            assert!(breakdown.merkle_roots > 0);
+            assert!(breakdown.out_of_domain_rows > 0);
+            assert!(breakdown.openings > 0);
+            assert!(breakdown.fri > 0);
+        }
+        assert!(proof.to_canonical_bytes().len() == comparison.left.total);
+        assert!(other_proof.to_canonical_bytes().len() == comparison.right.total);
+        assert!(comparison.left != comparison.right);
+
+        let table = comparison.to_string();
+        for (label, _) in comparison.left.components() {
+            assert!(table.contains(label));
+        }
+    }
+
+    #[proptest]
+    fn proof_survives_canonical_byte_round_trip(#[strategy(arb())] proof: Proof) {
+        let bytes = proof.to_canonical_bytes();
+        prop_assert_eq!(proof.0.len() * BFieldElement::BYTES, bytes.len());
+        prop_assert_eq!(proof, Proof::from_canonical_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn canonical_bytes_of_proof_are_little_endian_element_values() {
+        let proof = Proof(bfe_vec![1, BFieldElement::MAX]);
+        let bytes = proof.to_canonical_bytes();
+        assert!(1_u64.to_le_bytes() == bytes[..8]);
+        assert!(BFieldElement::MAX.to_le_bytes() == bytes[8..]);
+    }
+
+    #[test]
+    fn non_canonical_bytes_are_rejected_when_decoding_proof() {
+        let bytes = BFieldElement::P.to_le_bytes();
+        let_assert!(Err(err) = Proof::from_canonical_bytes(&bytes));
+        assert!(ParseBFieldElementError::NotCanonical(BFieldElement::P) == err);
+    }
+
+    #[test]
+    fn incomplete_bytes_are_rejected_when_decoding_proof() {
+        let bytes = [0; BFieldElement::BYTES + 3];
+        let_assert!(Err(err) = Proof::from_canonical_bytes(&bytes));
+        assert!(ParseBFieldElementError::InvalidNumBytes(3) == err);
+    }
+
     #[proptest]
     fn decode_proof(#[strategy(arb())] proof: Proof) {
         let encoded = proof.encode();
@@ -5,26 +5,125 @@ use crate::error::ProofStreamError;
 use crate::proof::Proof;
 use crate::proof_item::ProofItem;
 
+/// The Fiat-Shamir transcript of a [`ProofStream`]: absorbs everything the prover commits to
+/// and produces the verifier's challenges from it.
+///
+/// The default transcript is a [`Tip5`] sponge; using anything else produces proofs that are
+/// only meaningful to a verifier using the same kind of transcript. Custom transcripts are
+/// mostly useful for testing, for example by [recording](RecordingTranscript) the derivation
+/// of challenges or by seeding the transcript externally.
+///
+/// The [`Default`] value of an implementor is the transcript's initial state.
+pub trait Transcript: Default {
+    /// Absorb the given elements, padding them as necessary.
+    fn absorb_all(&mut self, input: &[BFieldElement]);
+
+    /// Produce `num_indices` uniform random numbers in the interval `[0; upper_bound)`, where
+    /// `upper_bound` is a power of two.
+    fn sample_indices(&mut self, upper_bound: u32, num_indices: usize) -> Vec<u32>;
+
+    /// Produce `num_scalars` uniform random [`XFieldElement`]s.
+    fn sample_scalars(&mut self, num_scalars: usize) -> Vec<XFieldElement>;
+}
+
+impl Transcript for Tip5 {
+    fn absorb_all(&mut self, input: &[BFieldElement]) {
+        self.pad_and_absorb_all(input)
+    }
+
+    fn sample_indices(&mut self, upper_bound: u32, num_indices: usize) -> Vec<u32> {
+        Tip5::sample_indices(self, upper_bound, num_indices)
+    }
+
+    fn sample_scalars(&mut self, num_scalars: usize) -> Vec<XFieldElement> {
+        Tip5::sample_scalars(self, num_scalars)
+    }
+}
+
+/// A single interaction with a [`Transcript`], as recorded by a [`RecordingTranscript`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TranscriptEvent {
+    Absorb(Vec<BFieldElement>),
+    SampleIndices(Vec<u32>),
+    SampleScalars(Vec<XFieldElement>),
+}
+
+/// A [`Transcript`] that forwards to some inner transcript and records every interaction with
+/// it. Comparing the recordings of prover and verifier, or of different versions of either,
+/// reveals any divergence in the derivation of challenges.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct RecordingTranscript<T: Transcript = Tip5> {
+    pub inner: T,
+    pub events: Vec<TranscriptEvent>,
+}
+
+impl<T: Transcript> RecordingTranscript<T> {
+    pub fn new(inner: T) -> Self {
+        let events = vec![];
+        Self { inner, events }
+    }
+}
+
+impl<T: Transcript> Transcript for RecordingTranscript<T> {
+    fn absorb_all(&mut self, input: &[BFieldElement]) {
+        self.inner.absorb_all(input);
+        self.events.push(TranscriptEvent::Absorb(input.to_vec()));
+    }
+
+    fn sample_indices(&mut self, upper_bound: u32, num_indices: usize) -> Vec<u32> {
+        let indices = self.inner.sample_indices(upper_bound, num_indices);
+        self.events
+            .push(TranscriptEvent::SampleIndices(indices.clone()));
+        indices
+    }
+
+    fn sample_scalars(&mut self, num_scalars: usize) -> Vec<XFieldElement> {
+        let scalars = self.inner.sample_scalars(num_scalars);
+        self.events
+            .push(TranscriptEvent::SampleScalars(scalars.clone()));
+        scalars
+    }
+}
+
 #[derive(Debug, Default, Clone, Eq, PartialEq, BFieldCodec, Arbitrary)]
-pub struct ProofStream {
+pub struct ProofStream<T: Transcript = Tip5> {
     pub items: Vec<ProofItem>,
 
     #[bfield_codec(ignore)]
     pub items_index: usize,
 
     #[bfield_codec(ignore)]
-    pub sponge: Tip5,
+    pub transcript: T,
 }
 
 impl ProofStream {
     pub fn new() -> Self {
+        Self::with_transcript(Tip5::init())
+    }
+}
+
+impl<T: Transcript> ProofStream<T> {
+    /// A new, empty proof stream using the given [`Transcript`] for the Fiat-Shamir heuristic.
+    pub fn with_transcript(transcript: T) -> Self {
         ProofStream {
             items: vec![],
             items_index: 0,
-            sponge: Tip5::init(),
+            transcript,
         }
     }
 
+    /// Like [`ProofStream::try_from`], but uses the given [`Transcript`] for the Fiat-Shamir
+    /// heuristic.
+    pub fn from_proof_with_transcript(
+        proof: &Proof,
+        transcript: T,
+    ) -> Result<Self, ProofStreamError> {
+        let ProofStream { items, .. } = ProofStream::<Tip5>::try_from(proof)?;
+        let mut proof_stream = Self::with_transcript(transcript);
+        proof_stream.items = items;
+        Ok(proof_stream)
+    }
+
     /// The number of field elements required to encode the proof.
     pub fn transcript_length(&self) -> usize {
         let Proof(b_field_elements) = self.into();
@@ -38,7 +137,7 @@ impl ProofStream {
     ///
     /// See also [`Self::enqueue()`] and [`Self::dequeue()`].
     pub fn alter_fiat_shamir_state_with(&mut self, item: &impl BFieldCodec) {
-        self.sponge.pad_and_absorb_all(&item.encode())
+        self.transcript.absorb_all(&item.encode())
     }
 
     /// Send a proof item as prover to verifier.
@@ -80,16 +179,16 @@ impl ProofStream {
     pub fn sample_indices(&mut self, upper_bound: usize, num_indices: usize) -> Vec<usize> {
         assert!(upper_bound.is_power_of_two());
         assert!(upper_bound <= BFieldElement::MAX as usize);
-        self.sponge
+        self.transcript
             .sample_indices(upper_bound as u32, num_indices)
             .into_iter()
             .map(|i| i as usize)
             .collect()
     }
 
-    /// A thin wrapper around [`Tip5::sample_scalars`].
+    /// A thin wrapper around [`Transcript::sample_scalars`].
     pub fn sample_scalars(&mut self, num_scalars: usize) -> Vec<XFieldElement> {
-        self.sponge.sample_scalars(num_scalars)
+        self.transcript.sample_scalars(num_scalars)
     }
 }
 
@@ -102,14 +201,14 @@ impl TryFrom<&Proof> for ProofStream {
     }
 }
 
-impl From<&ProofStream> for Proof {
-    fn from(proof_stream: &ProofStream) -> Self {
+impl<T: Transcript> From<&ProofStream<T>> for Proof {
+    fn from(proof_stream: &ProofStream<T>) -> Self {
         Proof(proof_stream.encode())
     }
 }
 
-impl From<ProofStream> for Proof {
-    fn from(proof_stream: ProofStream) -> Self {
+impl<T: Transcript> From<ProofStream<T>> for Proof {
+    fn from(proof_stream: ProofStream<T>) -> Self {
         (&proof_stream).into()
     }
 }
@@ -152,68 +251,68 @@ mod tests {
         let mut sponge_states = VecDeque::new();
         let mut proof_stream = ProofStream::new();
 
-        sponge_states.push_back(proof_stream.sponge.state);
+        sponge_states.push_back(proof_stream.transcript.state);
         proof_stream.enqueue(ProofItem::AuthenticationStructure(auth_structure.clone()));
-        sponge_states.push_back(proof_stream.sponge.state);
+        sponge_states.push_back(proof_stream.transcript.state);
         proof_stream.enqueue(ProofItem::MasterMainTableRows(main_rows.clone()));
-        sponge_states.push_back(proof_stream.sponge.state);
+        sponge_states.push_back(proof_stream.transcript.state);
         proof_stream.enqueue(ProofItem::MasterAuxTableRows(aux_rows.clone()));
-        sponge_states.push_back(proof_stream.sponge.state);
+        sponge_states.push_back(proof_stream.transcript.state);
         proof_stream.enqueue(ProofItem::OutOfDomainMainRow(ood_main_row.clone()));
-        sponge_states.push_back(proof_stream.sponge.state);
+        sponge_states.push_back(proof_stream.transcript.state);
         proof_stream.enqueue(ProofItem::OutOfDomainAuxRow(ood_aux_row.clone()));
-        sponge_states.push_back(proof_stream.sponge.state);
+        sponge_states.push_back(proof_stream.transcript.state);
         proof_stream.enqueue(ProofItem::MerkleRoot(root));
-        sponge_states.push_back(proof_stream.sponge.state);
+        sponge_states.push_back(proof_stream.transcript.state);
         proof_stream.enqueue(ProofItem::QuotientSegmentsElements(quot_elements.clone()));
-        sponge_states.push_back(proof_stream.sponge.state);
+        sponge_states.push_back(proof_stream.transcript.state);
         proof_stream.enqueue(ProofItem::FriCodeword(fri_codeword.clone()));
-        sponge_states.push_back(proof_stream.sponge.state);
+        sponge_states.push_back(proof_stream.transcript.state);
         proof_stream.enqueue(ProofItem::FriResponse(fri_response.clone()));
-        sponge_states.push_back(proof_stream.sponge.state);
+        sponge_states.push_back(proof_stream.transcript.state);
 
         let proof = proof_stream.into();
         let mut proof_stream = ProofStream::try_from(&proof).unwrap();
 
-        assert!(sponge_states.pop_front() == Some(proof_stream.sponge.state));
+        assert!(sponge_states.pop_front() == Some(proof_stream.transcript.state));
         let_assert!(Ok(proof_item) = proof_stream.dequeue());
         let_assert!(ProofItem::AuthenticationStructure(auth_structure_) = proof_item);
         assert!(auth_structure == auth_structure_);
 
-        assert!(sponge_states.pop_front() == Some(proof_stream.sponge.state));
+        assert!(sponge_states.pop_front() == Some(proof_stream.transcript.state));
         let_assert!(Ok(ProofItem::MasterMainTableRows(main_rows_)) = proof_stream.dequeue());
         assert!(main_rows == main_rows_);
 
-        assert!(sponge_states.pop_front() == Some(proof_stream.sponge.state));
+        assert!(sponge_states.pop_front() == Some(proof_stream.transcript.state));
         let_assert!(Ok(ProofItem::MasterAuxTableRows(aux_rows_)) = proof_stream.dequeue());
         assert!(aux_rows == aux_rows_);
 
-        assert!(sponge_states.pop_front() == Some(proof_stream.sponge.state));
+        assert!(sponge_states.pop_front() == Some(proof_stream.transcript.state));
         let_assert!(Ok(ProofItem::OutOfDomainMainRow(ood_main_row_)) = proof_stream.dequeue());
         assert!(ood_main_row == ood_main_row_);
 
-        assert!(sponge_states.pop_front() == Some(proof_stream.sponge.state));
+        assert!(sponge_states.pop_front() == Some(proof_stream.transcript.state));
         let_assert!(Ok(ProofItem::OutOfDomainAuxRow(ood_aux_row_)) = proof_stream.dequeue());
         assert!(ood_aux_row == ood_aux_row_);
 
-        assert!(sponge_states.pop_front() == Some(proof_stream.sponge.state));
+        assert!(sponge_states.pop_front() == Some(proof_stream.transcript.state));
         let_assert!(Ok(ProofItem::MerkleRoot(root_)) = proof_stream.dequeue());
         assert!(root == root_);
 
-        assert!(sponge_states.pop_front() == Some(proof_stream.sponge.state));
+        assert!(sponge_states.pop_front() == Some(proof_stream.transcript.state));
         let_assert!(Ok(proof_item) = proof_stream.dequeue());
         let_assert!(ProofItem::QuotientSegmentsElements(quot_elements_) = proof_item);
         assert!(quot_elements == quot_elements_);
 
-        assert!(sponge_states.pop_front() == Some(proof_stream.sponge.state));
+        assert!(sponge_states.pop_front() == Some(proof_stream.transcript.state));
         let_assert!(Ok(ProofItem::FriCodeword(fri_codeword_)) = proof_stream.dequeue());
         assert!(fri_codeword == fri_codeword_);
 
-        assert!(sponge_states.pop_front() == Some(proof_stream.sponge.state));
+        assert!(sponge_states.pop_front() == Some(proof_stream.transcript.state));
         let_assert!(Ok(ProofItem::FriResponse(fri_response_)) = proof_stream.dequeue());
         assert!(fri_response == fri_response_);
 
-        assert!(sponge_states.pop_front() == Some(proof_stream.sponge.state));
+        assert!(sponge_states.pop_front() == Some(proof_stream.transcript.state));
         assert!(0 == sponge_states.len());
     }
 
@@ -281,6 +380,6 @@ mod tests {
 
     #[test]
     fn encoded_length_of_prove_stream_is_not_known_at_compile_time() {
-        assert!(ProofStream::static_length().is_none());
+        assert!(ProofStream::<Tip5>::static_length().is_none());
     }
 }
@@ -1,6 +1,7 @@
 use assert2::assert;
 use assert2::let_assert;
 use isa::program::Program;
+use itertools::Itertools;
 use num_traits::Zero;
 use proptest::collection::vec;
 use proptest::prelude::*;
@@ -202,4 +203,25 @@ impl ProgramAndInput {
         let non_determinism = self.non_determinism();
         VM::run(self.program, public_input, non_determinism)
     }
+
+    /// Run the program and compare its observable behavior – the public output, the
+    /// final op stack, and the number of executed cycles – to the committed snapshot
+    /// with the given name. Any change in behavior shows up as a snapshot diff.
+    ///
+    /// Snapshots are managed by [`insta`]. To accept new or changed snapshots, run the
+    /// tests with environment variable `INSTA_UPDATE=always`, or use `cargo insta review`.
+    pub fn assert_run_matches_snapshot(self, snapshot_name: &str) {
+        let mut vm_state = VMState::new(self.program, self.public_input, self.non_determinism);
+        let_assert!(Ok(()) = vm_state.run());
+
+        let public_output = vm_state.public_output.iter().join(", ");
+        let final_stack = vm_state.op_stack.stack.iter().join(", ");
+        let cycle_count = vm_state.cycle_count;
+        let summary = format!(
+            "public output: [{public_output}]\n\
+             final stack:   [{final_stack}]\n\
+             cycle count:   {cycle_count}\n"
+        );
+        insta::assert_snapshot!(snapshot_name, summary);
+    }
 }
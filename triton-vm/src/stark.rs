@@ -1,7 +1,14 @@
+use std::any::Any;
 use std::ops::Mul;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
 use arbitrary::Arbitrary;
 use arbitrary::Unstructured;
+use isa::program::Program;
 use itertools::izip;
 use itertools::Itertools;
 use ndarray::prelude::*;
@@ -25,6 +32,8 @@ use crate::aet::AlgebraicExecutionTrace;
 use crate::arithmetic_domain::ArithmeticDomain;
 use crate::challenges::Challenges;
 use crate::error::ProvingError;
+use crate::error::StarkParameterError;
+use crate::error::VMError;
 use crate::error::VerificationError;
 use crate::fri;
 use crate::fri::Fri;
@@ -34,6 +43,8 @@ use crate::proof::Claim;
 use crate::proof::Proof;
 use crate::proof_item::ProofItem;
 use crate::proof_stream::ProofStream;
+use crate::proof_stream::Transcript;
+use crate::table;
 use crate::table::auxiliary_table::Evaluable;
 use crate::table::master_table::all_quotients_combined;
 use crate::table::master_table::interpolant_degree;
@@ -42,7 +53,11 @@ use crate::table::master_table::randomized_trace_len;
 use crate::table::master_table::MasterAuxTable;
 use crate::table::master_table::MasterMainTable;
 use crate::table::master_table::MasterTable;
+use crate::table::op_stack;
 use crate::table::QuotientSegments;
+use crate::vm::NonDeterminism;
+use crate::vm::PublicInput;
+use crate::vm::VM;
 
 /// The number of segments the quotient polynomial is split into.
 /// Helps keeping the FRI domain small.
@@ -82,6 +97,19 @@ pub struct Stark {
     pub num_collinearity_checks: usize,
 }
 
+/// The dimensions of a [proof](Proof) for some program and its inputs, as
+/// [estimated](Stark::estimate_proof_size) without proving.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
+pub struct ProofSizeEstimate {
+    /// The height of the [execution trace](AlgebraicExecutionTrace) after padding.
+    /// See also [`AlgebraicExecutionTrace::padded_height`].
+    pub padded_height: usize,
+
+    /// The length of the [FRI domain](Stark::fri). Has a major influence on
+    /// proving time as well as on memory consumption during proving.
+    pub fri_domain_length: usize,
+}
+
 /// The prover for Triton VM's [zk-STARK](Stark). The core method is
 /// [`prove`](Prover::prove). It is probably more convenient to call
 /// [`Stark::prove`] directly.
@@ -104,6 +132,44 @@ pub struct Prover {
     randomness_seed: <StdRng as SeedableRng>::Seed,
 }
 
+/// Allows cancelling an in-progress [proof](Prover::prove_cancellable), for example,
+/// from a different thread. Clones of a token share the same cancellation state.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation of every proof using this token or any of its clones.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    fn ensure_not_cancelled(&self) -> Result<(), ProvingError> {
+        if self.is_cancelled() {
+            return Err(ProvingError::Cancelled);
+        }
+        Ok(())
+    }
+}
+
+/// The message of a caught panic, if the panic's payload is a string.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
 /// The verifier for Triton VM's [zs-STARK](Stark). The core method is
 /// [`verify`](Verifier::verify). It is probably more convenient to call
 /// [`Stark::verify`] directly.
@@ -155,8 +221,91 @@ impl Prover {
         claim: &Claim,
         aet: &AlgebraicExecutionTrace,
     ) -> Result<Proof, ProvingError> {
+        let (proof, _) = self.prove_with_transcript(claim, aet, Tip5::init())?;
+        Ok(proof)
+    }
+
+    /// Like [`prove`](Self::prove), but aborts with [`ProvingError::Cancelled`] once
+    /// the given [`CancellationToken`] is [cancelled](CancellationToken::cancel).
+    ///
+    /// The token is only checked between the major phases of proof generation.
+    /// Depending on the size of the trace, some time might pass between cancelling
+    /// the token and this method returning.
+    pub fn prove_cancellable(
+        self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        cancellation: &CancellationToken,
+    ) -> Result<Proof, ProvingError> {
+        let (proof, _) = self.prove_inner(claim, aet, Tip5::init(), cancellation, false)?;
+        Ok(proof)
+    }
+
+    /// Like [`prove`](Self::prove), but converts any panic during proof generation
+    /// into a [`ProvingError::Internal`]. A malformed witness cannot abort the host
+    /// process this way.
+    ///
+    /// The panic is still reported by the current panic hook, which by default prints
+    /// the panic message to standard error. Panics can only be caught if the binary
+    /// is compiled with `panic = "unwind"`, which is the default.
+    pub fn prove_catching_panics(
+        self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+    ) -> Result<Proof, ProvingError> {
+        panic::catch_unwind(AssertUnwindSafe(|| self.prove(claim, aet)))
+            .unwrap_or_else(|payload| Err(ProvingError::Internal(panic_message(payload))))
+    }
+
+    /// Like [`prove`](Self::prove), but additionally evaluates every [AIR](air)
+    /// constraint on the execution trace before doing any FRI work. Helps when
+    /// developing new constraints: a constraint that does not hold for an honest
+    /// trace is detected immediately, not only once verification fails.
+    ///
+    /// Evaluating the constraints is considerably slower than proving.
+    ///
+    /// # Panics
+    ///
+    /// Panics at the first violated constraint, naming the table, the constraint,
+    /// and the row it is violated on.
+    pub fn prove_with_checks(
+        self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+    ) -> Result<Proof, ProvingError> {
+        let cancellation = CancellationToken::new();
+        let (proof, _) = self.prove_inner(claim, aet, Tip5::init(), &cancellation, true)?;
+        Ok(proof)
+    }
+
+    /// Like [`prove`](Self::prove), but uses the given [`Transcript`] for the
+    /// Fiat-Shamir heuristic. Returns the transcript in its final state alongside
+    /// the proof.
+    ///
+    /// Unless the transcript behaves exactly like a fresh [`Tip5`] sponge, the
+    /// resulting proof can only be verified using
+    /// [`Verifier::verify_with_transcript`] and a corresponding transcript.
+    pub fn prove_with_transcript<T: Transcript>(
+        self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        transcript: T,
+    ) -> Result<(Proof, T), ProvingError> {
+        self.prove_inner(claim, aet, transcript, &CancellationToken::new(), false)
+    }
+
+    fn prove_inner<T: Transcript>(
+        self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+        transcript: T,
+        cancellation: &CancellationToken,
+        check_constraints: bool,
+    ) -> Result<(Proof, T), ProvingError> {
+        cancellation.ensure_not_cancelled()?;
+
         profiler!(start "Fiat-Shamir: claim" ("hash"));
-        let mut proof_stream = ProofStream::new();
+        let mut proof_stream = ProofStream::with_transcript(transcript);
         proof_stream.alter_fiat_shamir_state_with(claim);
         profiler!(stop "Fiat-Shamir: claim");
 
@@ -169,6 +318,9 @@ impl Prover {
         profiler!(stop "derive additional parameters");
 
         profiler!(start "main tables");
+        op_stack::ensure_no_stack_pointer_below_register_count(
+            aet.op_stack_underflow_trace.view(),
+        )?;
         profiler!(start "create" ("gen"));
         let mut master_main_table = MasterMainTable::new(
             aet,
@@ -183,6 +335,7 @@ impl Prover {
         master_main_table.pad();
         profiler!(stop "pad");
 
+        cancellation.ensure_not_cancelled()?;
         master_main_table.maybe_low_degree_extend_all_columns();
 
         profiler!(start "Merkle tree");
@@ -195,9 +348,20 @@ impl Prover {
         let challenges = Challenges::new(challenges, claim);
         profiler!(stop "Fiat-Shamir");
 
+        cancellation.ensure_not_cancelled()?;
         profiler!(start "extend" ("gen"));
         let mut master_aux_table = master_main_table.extend(&challenges);
         profiler!(stop "extend");
+
+        if check_constraints {
+            profiler!(start "check constraints");
+            table::assert_all_constraints_hold(
+                master_main_table.trace_table(),
+                master_aux_table.trace_table(),
+                &challenges,
+            );
+            profiler!(stop "check constraints");
+        }
         profiler!(stop "main tables");
 
         profiler!(start "aux tables");
@@ -216,6 +380,7 @@ impl Prover {
         profiler!(stop "Fiat-Shamir");
         profiler!(stop "aux tables");
 
+        cancellation.ensure_not_cancelled()?;
         let (fri_domain_quotient_segment_codewords, quotient_segment_polynomials) =
             Self::compute_quotient_segments(
                 &mut master_main_table,
@@ -390,6 +555,7 @@ impl Prover {
         assert_eq!(fri.domain.length, fri_combination_codeword.len());
         profiler!(stop "combined DEEP polynomial");
 
+        cancellation.ensure_not_cancelled()?;
         profiler!(start "FRI");
         let revealed_current_row_indices =
             fri.prove(&fri_combination_codeword, &mut proof_stream)?;
@@ -451,7 +617,8 @@ impl Prover {
         ));
         profiler!(stop "open trace leafs");
 
-        Ok(proof_stream.into())
+        let proof = Proof::from(&proof_stream);
+        Ok((proof, proof_stream.transcript))
     }
 
     /// An [`ArithmeticDomain`] _just_ large enough to perform all the necessary
@@ -1050,8 +1217,39 @@ impl Verifier {
 
     /// See also [`Stark::verify`].
     pub fn verify(self, claim: &Claim, proof: &Proof) -> Result<(), VerificationError> {
+        self.verify_with_transcript(claim, proof, Tip5::init())?;
+        Ok(())
+    }
+
+    /// Verify every given proof against its claim, returning one verdict per proof, in
+    /// order. Equivalent to calling [`verify`](Self::verify) for every pair, except that
+    /// the proofs are verified in parallel.
+    ///
+    /// There is no work to share between proofs, even if they are all about the same
+    /// program: the program enters verification only through the program digest in the
+    /// [`Claim`], which is part of every proof's Fiat-Shamir transcript. Every proof is
+    /// verified with its own transcript, making a batch exactly as sound as verifying its
+    /// proofs one by one.
+    pub fn verify_batch(self, claims_and_proofs: &[(Claim, Proof)]) -> Vec<bool> {
+        claims_and_proofs
+            .par_iter()
+            .map(|(claim, proof)| self.verify(claim, proof).is_ok())
+            .collect()
+    }
+
+    /// Like [`verify`](Self::verify), but uses the given [`Transcript`] for the
+    /// Fiat-Shamir heuristic. Returns the transcript in its final state if the
+    /// proof is valid.
+    ///
+    /// See also [`Prover::prove_with_transcript`].
+    pub fn verify_with_transcript<T: Transcript>(
+        self,
+        claim: &Claim,
+        proof: &Proof,
+        transcript: T,
+    ) -> Result<T, VerificationError> {
         profiler!(start "deserialize");
-        let mut proof_stream = ProofStream::try_from(proof)?;
+        let mut proof_stream = ProofStream::from_proof_with_transcript(proof, transcript)?;
         profiler!(stop "deserialize");
 
         profiler!(start "Fiat-Shamir: Claim" ("hash"));
@@ -1346,7 +1544,7 @@ impl Verifier {
             profiler!(stop "combination codeword equality");
         }
         profiler!(stop "linear combination");
-        Ok(())
+        Ok(proof_stream.transcript)
     }
 
     fn hash_quotient_segment_elements(quotient_segment_rows: &[QuotientSegments]) -> Vec<Digest> {
@@ -1395,11 +1593,7 @@ impl Stark {
 
         let fri_expansion_factor = 1 << log2_of_fri_expansion_factor;
         let num_collinearity_checks = security_level / log2_of_fri_expansion_factor;
-
-        let num_out_of_domain_rows = 2;
-        let num_trace_randomizers = num_collinearity_checks
-            + num_out_of_domain_rows * x_field_element::EXTENSION_DEGREE
-            + NUM_QUOTIENT_SEGMENTS * x_field_element::EXTENSION_DEGREE;
+        let num_trace_randomizers = Self::min_num_trace_randomizers(num_collinearity_checks);
 
         Stark {
             security_level,
@@ -1409,6 +1603,106 @@ impl Stark {
         }
     }
 
+    /// The smallest number of trace randomizers that achieves zero-knowledge given
+    /// the number of collinearity checks in [FRI](Fri).
+    fn min_num_trace_randomizers(num_collinearity_checks: usize) -> usize {
+        let num_out_of_domain_rows = 2;
+
+        num_collinearity_checks
+            + num_out_of_domain_rows * x_field_element::EXTENSION_DEGREE
+            + NUM_QUOTIENT_SEGMENTS * x_field_element::EXTENSION_DEGREE
+    }
+
+    /// Use the given FRI expansion factor, a trade-off between proving time (small
+    /// factors) and proof size as well as verification time (large factors).
+    ///
+    /// Prover and verifier must use identical parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the expansion factor is not a power of 2 greater than 1,
+    /// or if the resulting parameters fall short of the
+    /// [security level](Self::security_level).
+    pub fn with_fri_expansion_factor(
+        mut self,
+        fri_expansion_factor: usize,
+    ) -> Result<Self, StarkParameterError> {
+        if fri_expansion_factor <= 1 || !fri_expansion_factor.is_power_of_two() {
+            return Err(StarkParameterError::UnsupportedFriExpansionFactor(
+                fri_expansion_factor,
+            ));
+        }
+
+        self.fri_expansion_factor = fri_expansion_factor;
+        self.ensure_security_level_is_achieved()?;
+        Ok(self)
+    }
+
+    /// Use the given number of collinearity checks in [FRI](Fri). If necessary, the
+    /// number of trace randomizers is increased to retain zero-knowledge.
+    ///
+    /// Prover and verifier must use identical parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resulting parameters fall short of the
+    /// [security level](Self::security_level).
+    pub fn with_num_collinearity_checks(
+        mut self,
+        num_collinearity_checks: usize,
+    ) -> Result<Self, StarkParameterError> {
+        self.num_collinearity_checks = num_collinearity_checks;
+        let min_num_trace_randomizers = Self::min_num_trace_randomizers(num_collinearity_checks);
+        self.num_trace_randomizers = self.num_trace_randomizers.max(min_num_trace_randomizers);
+
+        self.ensure_security_level_is_achieved()?;
+        Ok(self)
+    }
+
+    /// Use the given number of trace randomizers.
+    ///
+    /// Prover and verifier must use identical parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the number of trace randomizers is too small to achieve
+    /// zero-knowledge given the number of collinearity checks.
+    pub fn with_num_trace_randomizers(
+        mut self,
+        num_trace_randomizers: usize,
+    ) -> Result<Self, StarkParameterError> {
+        let min = Self::min_num_trace_randomizers(self.num_collinearity_checks);
+        if num_trace_randomizers < min {
+            return Err(StarkParameterError::TooFewTraceRandomizers {
+                min,
+                actual: num_trace_randomizers,
+            });
+        }
+
+        self.num_trace_randomizers = num_trace_randomizers;
+        Ok(self)
+    }
+
+    /// The number of bits of security the parameters achieve. Can differ from the
+    /// targeted [security level](Self::security_level) because the number of
+    /// collinearity checks is an integer.
+    pub fn achieved_security_level(&self) -> usize {
+        let log2_of_fri_expansion_factor = self.fri_expansion_factor.ilog2() as usize;
+        self.num_collinearity_checks * log2_of_fri_expansion_factor
+    }
+
+    fn ensure_security_level_is_achieved(&self) -> Result<(), StarkParameterError> {
+        let achieved = self.achieved_security_level();
+        if achieved < self.security_level {
+            return Err(StarkParameterError::InsufficientSecurity {
+                required: self.security_level,
+                achieved,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Prove the correctness of the given [Claim] using the given
     /// [witness](AlgebraicExecutionTrace).
     ///
@@ -1423,6 +1717,18 @@ impl Stark {
         Prover::new(*self).prove(claim, aet)
     }
 
+    /// Like [`prove`](Self::prove), but additionally evaluates every [AIR](air)
+    /// constraint on the execution trace, panicking at the first violation.
+    ///
+    /// See also [`Prover::prove_with_checks`].
+    pub fn prove_with_checks(
+        &self,
+        claim: &Claim,
+        aet: &AlgebraicExecutionTrace,
+    ) -> Result<Proof, ProvingError> {
+        Prover::new(*self).prove_with_checks(claim, aet)
+    }
+
     /// Verify the accuracy of the given [Claim], supported by the [Proof].
     ///
     /// See also [`Verifier`].
@@ -1431,6 +1737,13 @@ impl Stark {
         Verifier::new(*self).verify(claim, proof)
     }
 
+    /// Verify a batch of [Claim]s, each supported by a [Proof].
+    ///
+    /// See also [`Verifier::verify_batch`].
+    pub fn verify_batch(&self, claims_and_proofs: &[(Claim, Proof)]) -> Vec<bool> {
+        Verifier::new(*self).verify_batch(claims_and_proofs)
+    }
+
     /// The upper bound to use for the maximum degree the quotients given the length
     /// of the trace and the number of trace randomizers. The degree of the
     /// quotients depends on the [AIR](air) constraints.
@@ -1469,6 +1782,33 @@ impl Stark {
         )
     }
 
+    /// Estimate the dimensions of a [proof](Proof) for the given program and inputs
+    /// without proving. Useful for predicting proving time and memory consumption.
+    ///
+    /// The estimate is exact: the program is [traced](VM::trace_execution), and
+    /// the padded height is the padded height of the longest table, not only of
+    /// the processor table. Tracing is much cheaper than proving.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if execution of the program fails.
+    pub fn estimate_proof_size(
+        &self,
+        program: Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> Result<ProofSizeEstimate, VMError> {
+        let (aet, _) = VM::trace_execution(program, public_input, non_determinism)?;
+        let padded_height = aet.padded_height();
+        let fri_domain_length = self.fri_expansion_factor
+            * randomized_trace_len(padded_height, self.num_trace_randomizers);
+
+        Ok(ProofSizeEstimate {
+            padded_height,
+            fri_domain_length,
+        })
+    }
+
     /// Given `f(x)` (the in-domain evaluation of polynomial `f` in `x`), the domain point `x` at
     /// which polynomial `f` was evaluated, the out-of-domain evaluation `f(α)`, and the
     /// out-of-domain domain point `α`, apply the DEEP update: `(f(x) - f(α)) / (x - α)`.
@@ -1528,7 +1868,7 @@ impl LinearCombinationWeights {
         + NUM_QUOTIENT_SEGMENTS
         + NUM_DEEP_CODEWORD_COMPONENTS;
 
-    fn sample(proof_stream: &mut ProofStream) -> Self {
+    fn sample<T: Transcript>(proof_stream: &mut ProofStream<T>) -> Self {
         const MAIN_END: usize = MasterMainTable::NUM_COLUMNS;
         const AUX_END: usize = MAIN_END + MasterAuxTable::NUM_COLUMNS;
         const QUOT_END: usize = AUX_END + NUM_QUOTIENT_SEGMENTS;
@@ -1570,6 +1910,7 @@ pub(crate) mod tests {
     use air::table::ram::RamTable;
     use air::table::u32::U32Table;
     use air::table::TableId;
+    use air::table_column::JumpStackMainColumn;
     use air::table_column::MasterAuxColumn;
     use air::table_column::MasterMainColumn;
     use air::table_column::OpStackMainColumn;
@@ -1601,7 +1942,10 @@ pub(crate) mod tests {
 
     use super::*;
     use crate::config::CacheDecision;
+    use crate::error::FriValidationError;
     use crate::error::InstructionError;
+    use crate::proof_stream::RecordingTranscript;
+    use crate::proof_stream::TranscriptEvent;
     use crate::shared_tests::construct_master_main_table;
     use crate::shared_tests::low_security_stark;
     use crate::shared_tests::prove_and_verify;
@@ -1817,6 +2161,265 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn proving_same_claim_twice_with_same_seed_gives_byte_identical_proofs() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program).with_input(bfe_vec![3]);
+        let input = claim.input.clone().into();
+        let (aet, output) = VM::trace_execution(program, input, [].into()).unwrap();
+        let claim = claim.with_output(output);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let seed = StdRng::seed_from_u64(1528097203758146208).gen();
+        let prove = || {
+            Prover::new(stark)
+                .set_randomness_seed_which_may_break_zero_knowledge(seed)
+                .prove(&claim, &aet)
+                .unwrap()
+        };
+
+        let proof = prove();
+        let other_proof = prove();
+        assert!(proof.to_canonical_bytes() == other_proof.to_canonical_bytes());
+    }
+
+    #[test]
+    fn single_traced_execution_provides_both_output_and_provable_trace() {
+        let program = crate::example_programs::FIBONACCI_SEQUENCE.clone();
+        let input = PublicInput::from(bfe_array![23]);
+        let_assert!(Ok(run_output) = VM::run(program.clone(), input.clone(), [].into()));
+
+        let claim = Claim::about_program(&program).with_input(input.clone());
+        let_assert!(Ok((aet, trace_output)) = VM::trace_execution(program, input, [].into()));
+        assert!(run_output == trace_output);
+
+        let claim = claim.with_output(trace_output);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let_assert!(Ok(proof) = Prover::new(stark).prove(&claim, &aet));
+        assert!(let Ok(()) = Verifier::new(stark).verify(&claim, &proof));
+    }
+
+    #[test]
+    fn cancelled_proof_generation_aborts_with_cancellation_error() {
+        let program = triton_program!(halt);
+        let claim = Claim::about_program(&program);
+        let (aet, _) = VM::trace_execution(program, [].into(), [].into()).unwrap();
+
+        let cancellation = CancellationToken::new();
+        cancellation.clone().cancel();
+        assert!(cancellation.is_cancelled());
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let prover = Prover::new(stark);
+        let_assert!(Err(err) = prover.prove_cancellable(&claim, &aet, &cancellation));
+        let_assert!(ProvingError::Cancelled = err);
+    }
+
+    #[test]
+    fn proving_trace_with_op_stack_underflow_gives_descriptive_error() {
+        let program = triton_program!(push 1 push 2 pop 2 halt);
+        let claim = Claim::about_program(&program);
+        let (mut aet, _) = VM::trace_execution(program, [].into(), [].into()).unwrap();
+        let stack_pointer_index = OpStackMainColumn::StackPointer.main_index();
+        aet.op_stack_underflow_trace[[0, stack_pointer_index]] = bfe!(0);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let_assert!(Err(err) = stark.prove(&claim, &aet));
+        let_assert!(
+            ProvingError::OpStackUnderflow {
+                stack_pointer: 0,
+                ..
+            } = err
+        );
+    }
+
+    #[test]
+    fn panic_during_proof_generation_is_converted_into_error() {
+        let program = triton_program!(push 7 read_mem 1 pop 2 halt);
+        let claim = Claim::about_program(&program);
+        let (mut aet, _) = VM::trace_execution(program, [].into(), [].into()).unwrap();
+        aet.ram_trace = Array2::zeros([1, 2]);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let prover = Prover::new(stark);
+        let_assert!(Err(err) = prover.prove_catching_panics(&claim, &aet));
+        let_assert!(ProvingError::Internal(message) = err);
+        assert!(!message.is_empty());
+    }
+
+    #[test]
+    fn catching_panics_does_not_interfere_with_regular_proof_generation() {
+        let program = triton_program!(halt);
+        let claim = Claim::about_program(&program);
+        let (aet, _) = VM::trace_execution(program, [].into(), [].into()).unwrap();
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let_assert!(Ok(proof) = Prover::new(stark).prove_catching_panics(&claim, &aet));
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+    }
+
+    #[test]
+    fn proof_size_estimate_matches_dimensions_of_actual_proof() {
+        let program = triton_program!(read_io 1 push 1 add sponge_init write_io 1 halt);
+        let public_input = PublicInput::new(bfe_vec![41]);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let_assert!(
+            Ok(estimate) =
+                stark.estimate_proof_size(program.clone(), public_input.clone(), [].into())
+        );
+
+        let (aet, output) = VM::trace_execution(program, public_input.clone(), [].into()).unwrap();
+        let claim = Claim::about_program(&aet.program)
+            .with_input(public_input)
+            .with_output(output);
+        let proof = stark.prove(&claim, &aet).unwrap();
+
+        let_assert!(Ok(padded_height) = proof.padded_height());
+        assert!(padded_height == estimate.padded_height);
+        let fri = stark.fri(padded_height).unwrap();
+        assert!(fri.domain.length == estimate.fri_domain_length);
+    }
+
+    #[test]
+    fn stark_parameters_can_be_customized() {
+        let_assert!(
+            Ok(stark) = Stark::default()
+                .with_fri_expansion_factor(8)
+                .and_then(|stark| stark.with_num_collinearity_checks(60))
+                .and_then(|stark| stark.with_num_trace_randomizers(200))
+        );
+        assert!(8 == stark.fri_expansion_factor);
+        assert!(60 == stark.num_collinearity_checks);
+        assert!(200 == stark.num_trace_randomizers);
+        assert!(180 == stark.achieved_security_level());
+    }
+
+    #[test]
+    fn unsupported_stark_parameters_are_rejected() {
+        let stark = Stark::default();
+
+        for factor in [0, 1, 3, 12] {
+            let_assert!(Err(err) = stark.with_fri_expansion_factor(factor));
+            assert!(StarkParameterError::UnsupportedFriExpansionFactor(factor) == err);
+        }
+
+        let_assert!(Err(err) = stark.with_fri_expansion_factor(2));
+        let_assert!(StarkParameterError::InsufficientSecurity { required, achieved } = err);
+        assert!(160 == required);
+        assert!(80 == achieved);
+
+        let_assert!(Err(err) = stark.with_num_collinearity_checks(79));
+        let_assert!(StarkParameterError::InsufficientSecurity { .. } = err);
+
+        let num_trace_randomizers = stark.num_trace_randomizers - 1;
+        let_assert!(Err(err) = stark.with_num_trace_randomizers(num_trace_randomizers));
+        let_assert!(StarkParameterError::TooFewTraceRandomizers { .. } = err);
+    }
+
+    #[test]
+    fn increasing_number_of_collinearity_checks_retains_enough_trace_randomizers() {
+        let stark = Stark::default();
+        let_assert!(Ok(stark) = stark.with_num_collinearity_checks(100));
+        let_assert!(Ok(_) = stark.with_num_trace_randomizers(stark.num_trace_randomizers));
+        assert!(Stark::min_num_trace_randomizers(100) == stark.num_trace_randomizers);
+    }
+
+    #[test]
+    fn proof_made_with_one_expansion_factor_fails_verification_under_another() {
+        let program = triton_program!(push 3 push 4 mul write_io 1 halt);
+        let (aet, output) = VM::trace_execution(program.clone(), [].into(), [].into()).unwrap();
+        let claim = Claim::about_program(&program).with_output(output);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove(&claim, &aet).unwrap();
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+
+        let larger_factor = 2 * stark.fri_expansion_factor;
+        let_assert!(Ok(other_stark) = stark.with_fri_expansion_factor(larger_factor));
+        let_assert!(Err(err) = other_stark.verify(&claim, &proof));
+        let_assert!(VerificationError::FriValidationError(err) = err);
+        let_assert!(FriValidationError::IncorrectLastCodewordLength { .. } = err);
+    }
+
+    #[test]
+    fn proof_size_cannot_be_estimated_for_failing_program() {
+        let program = triton_program!(push 0 assert halt);
+        let stark = Stark::default();
+        let_assert!(Err(err) = stark.estimate_proof_size(program, [].into(), [].into()));
+        let_assert!(InstructionError::AssertionFailed(_) = err.source);
+    }
+
+    #[test]
+    fn proof_generation_with_uncancelled_token_succeeds() {
+        let program = triton_program!(halt);
+        let claim = Claim::about_program(&program);
+        let (aet, _) = VM::trace_execution(program, [].into(), [].into()).unwrap();
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let cancellation = CancellationToken::new();
+        let proof = Prover::new(stark)
+            .prove_cancellable(&claim, &aet, &cancellation)
+            .unwrap();
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+    }
+
+    #[test]
+    fn transcript_of_tiny_proof_is_stable_and_shared_by_prover_and_verifier() {
+        let program = triton_program!(halt);
+        let claim = Claim::about_program(&program);
+        let (aet, _) = VM::trace_execution(program, [].into(), [].into()).unwrap();
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let seed = StdRng::seed_from_u64(5182936548183041567).gen();
+        let prove = || {
+            Prover::new(stark)
+                .set_randomness_seed_which_may_break_zero_knowledge(seed)
+                .prove_with_transcript(&claim, &aet, RecordingTranscript::new(Tip5::init()))
+                .unwrap()
+        };
+
+        let (proof, prover_transcript) = prove();
+        let (_, other_prover_transcript) = prove();
+        assert!(prover_transcript == other_prover_transcript);
+
+        let verifier = Verifier::new(stark);
+        let_assert!(
+            Ok(verifier_transcript) = verifier.verify_with_transcript(
+                &claim,
+                &proof,
+                RecordingTranscript::new(Tip5::init())
+            )
+        );
+        assert!(prover_transcript.events == verifier_transcript.events);
+        assert!(prover_transcript.inner == verifier_transcript.inner);
+
+        let proof_stream = ProofStream::try_from(&proof).unwrap();
+        let_assert!(ProofItem::MerkleRoot(main_root) = &proof_stream.items[1]);
+        let_assert!(ProofItem::MerkleRoot(aux_root) = &proof_stream.items[2]);
+        let_assert!(ProofItem::MerkleRoot(quot_root) = &proof_stream.items[3]);
+
+        let absorb = |item: &ProofItem| TranscriptEvent::Absorb(item.encode());
+        let_assert!(
+            [
+                TranscriptEvent::Absorb(claim_encoding),
+                main_root_event,
+                TranscriptEvent::SampleScalars(challenges),
+                aux_root_event,
+                TranscriptEvent::SampleScalars(quotient_weights),
+                quot_root_event,
+                TranscriptEvent::SampleScalars(out_of_domain_point),
+                ..
+            ] = prover_transcript.events.as_slice()
+        );
+        assert!(&claim.encode() == claim_encoding);
+        assert!(&absorb(&ProofItem::MerkleRoot(*main_root)) == main_root_event);
+        assert!(Challenges::SAMPLE_COUNT == challenges.len());
+        assert!(&absorb(&ProofItem::MerkleRoot(*aux_root)) == aux_root_event);
+        assert!(MasterAuxTable::NUM_CONSTRAINTS == quotient_weights.len());
+        assert!(&absorb(&ProofItem::MerkleRoot(*quot_root)) == quot_root_event);
+        assert!(1 == out_of_domain_point.len());
+    }
+
     #[test]
     fn print_ram_table_example_for_specification() {
         let program = triton_program!(
@@ -2618,6 +3221,45 @@ pub(crate) mod tests {
         check_cross_table_constraints(mbt, met, &challenges);
     }
 
+    #[test]
+    fn tampered_jump_stack_clock_jump_breaks_clock_jump_difference_lookup() {
+        let (_, claim, mut master_main_table) =
+            master_main_table_for_low_security_level(test_program_for_call_recurse_return());
+        master_main_table.pad();
+
+        // Tamper with a clock jump that is looked up, i.e., one within the same jump
+        // stack pointer. Both affected clock jump differences stay positive.
+        let mut jump_stack_table = master_main_table.table_mut(TableId::JumpStack);
+        let clk = JumpStackMainColumn::CLK.main_index();
+        let jsp = JumpStackMainColumn::JSP.main_index();
+        let tampered_row_idx = (1..jump_stack_table.nrows() - 1)
+            .find(|&i| {
+                let row = |i| jump_stack_table.row(i);
+                row(i - 1)[jsp] == row(i)[jsp]
+                    && row(i)[jsp] == row(i + 1)[jsp]
+                    && row(i + 1)[clk].value() - row(i)[clk].value() > 1
+            })
+            .unwrap();
+        jump_stack_table[[tampered_row_idx, clk]] += bfe!(1);
+
+        let challenges = Challenges::placeholder(&claim);
+        let master_aux_table = master_main_table.extend(&challenges);
+        let mbt = master_main_table.trace_table();
+        let met = master_aux_table.trace_table();
+
+        let builder = ConstraintCircuitBuilder::new();
+        let_assert!(
+            Some(clock_jump_difference_lookup) =
+                GrandCrossTableArg::terminal_constraints(&builder).pop()
+        );
+        let terminal = clock_jump_difference_lookup.consume().evaluate(
+            mbt.slice(s![-1.., ..]),
+            met.slice(s![-1.., ..]),
+            &challenges.challenges,
+        );
+        assert!(xfe!(0) != terminal);
+    }
+
     #[test]
     fn derived_constraints_evaluate_to_zero_on_halt() {
         derived_constraints_evaluate_to_zero(test_program_for_halt());
@@ -2695,6 +3337,160 @@ pub(crate) mod tests {
         }
     }
 
+    /// A small program's claim, together with a valid proof for that claim.
+    fn claim_and_proof_to_tamper_with() -> (Stark, Claim, Proof) {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program).with_input(bfe_vec![3]);
+        let input = claim.input.clone().into();
+        let (aet, output) = VM::trace_execution(program, input, [].into()).unwrap();
+        let claim = claim.with_output(output);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove(&claim, &aet).unwrap();
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+
+        (stark, claim, proof)
+    }
+
+    /// Apply the given function to all items in the proof.
+    fn tamper_with_proof(proof: &Proof, tamper: impl Fn(&mut ProofItem)) -> Proof {
+        let mut proof_stream = ProofStream::try_from(proof).unwrap();
+        proof_stream.items.iter_mut().for_each(tamper);
+        proof_stream.into()
+    }
+
+    #[test]
+    fn verifying_truncated_proof_fails_with_deserialization_error() {
+        let (stark, claim, mut proof) = claim_and_proof_to_tamper_with();
+        proof.0.truncate(proof.0.len() / 2);
+
+        let_assert!(Err(err) = stark.verify(&claim, &proof));
+        let_assert!(VerificationError::ProofStreamError(_) = err);
+    }
+
+    #[test]
+    fn batch_verification_gives_independent_verdict_per_proof() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let claim_and_proof = |input: u64| {
+            let claim = Claim::about_program(&program).with_input(bfe_vec![input]);
+            let public_input = claim.input.clone().into();
+            let (aet, output) =
+                VM::trace_execution(program.clone(), public_input, [].into()).unwrap();
+            let claim = claim.with_output(output);
+            let proof = stark.prove(&claim, &aet).unwrap();
+            (claim, proof)
+        };
+
+        let mut claims_and_proofs = (0..4).map(claim_and_proof).collect_vec();
+        let (claim, _) = &mut claims_and_proofs[1];
+        *claim = claim.clone().with_output(bfe_vec![7]);
+        let (_, proof) = claims_and_proofs[0].clone();
+        claims_and_proofs[3].1 = proof;
+
+        let verdicts = stark.verify_batch(&claims_and_proofs);
+        assert!(vec![true, false, true, false] == verdicts);
+
+        let expected_verdicts = claims_and_proofs
+            .iter()
+            .map(|(claim, proof)| stark.verify(claim, proof).is_ok())
+            .collect_vec();
+        assert!(expected_verdicts == verdicts);
+    }
+
+    #[test]
+    fn proving_with_constraint_checks_gives_valid_proof_for_honest_trace() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program).with_input(bfe_vec![21]);
+        let public_input = claim.input.clone().into();
+        let (aet, output) = VM::trace_execution(program, public_input, [].into()).unwrap();
+        let claim = claim.with_output(output);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let proof = stark.prove_with_checks(&claim, &aet).unwrap();
+        assert!(let Ok(()) = stark.verify(&claim, &proof));
+    }
+
+    #[test]
+    #[should_panic(expected = "GrandCrossTableArg: Terminal constraint")]
+    fn proving_with_constraint_checks_panics_on_wrong_claim() {
+        let program = triton_program!(read_io 1 push 2 mul write_io 1 halt);
+        let claim = Claim::about_program(&program).with_input(bfe_vec![21]);
+        let public_input = claim.input.clone().into();
+        let (aet, _) = VM::trace_execution(program, public_input, [].into()).unwrap();
+        let claim = claim.with_output(bfe_vec![43]);
+
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        let _proof = stark.prove_with_checks(&claim, &aet);
+    }
+
+    #[test]
+    fn batch_verification_of_no_proofs_gives_no_verdicts() {
+        let stark = low_security_stark(DEFAULT_LOG2_FRI_EXPANSION_FACTOR_FOR_TESTS);
+        assert!(stark.verify_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn verifying_proof_for_different_claim_fails_with_quotient_mismatch() {
+        let (stark, claim, proof) = claim_and_proof_to_tamper_with();
+        let claim = claim.with_output(bfe_vec![7]);
+
+        let_assert!(Err(err) = stark.verify(&claim, &proof));
+        let_assert!(VerificationError::OutOfDomainQuotientValueMismatch = err);
+    }
+
+    #[test]
+    fn verifying_proof_with_tampered_fri_response_fails_with_fri_error() {
+        let (stark, claim, proof) = claim_and_proof_to_tamper_with();
+        let proof = tamper_with_proof(&proof, |item| {
+            if let ProofItem::FriResponse(response) = item {
+                response.revealed_leaves[0] += xfe!(1);
+            }
+        });
+
+        let_assert!(Err(err) = stark.verify(&claim, &proof));
+        let_assert!(VerificationError::FriValidationError(_) = err);
+    }
+
+    #[test]
+    fn verifying_proof_with_tampered_main_table_rows_fails_with_authentication_error() {
+        let (stark, claim, proof) = claim_and_proof_to_tamper_with();
+        let proof = tamper_with_proof(&proof, |item| {
+            if let ProofItem::MasterMainTableRows(rows) = item {
+                rows[0][0] += bfe!(1);
+            }
+        });
+
+        let_assert!(Err(err) = stark.verify(&claim, &proof));
+        let_assert!(VerificationError::MainCodewordAuthenticationFailure = err);
+    }
+
+    #[test]
+    fn verifying_proof_with_tampered_aux_table_rows_fails_with_authentication_error() {
+        let (stark, claim, proof) = claim_and_proof_to_tamper_with();
+        let proof = tamper_with_proof(&proof, |item| {
+            if let ProofItem::MasterAuxTableRows(rows) = item {
+                rows[0][0] += xfe!(1);
+            }
+        });
+
+        let_assert!(Err(err) = stark.verify(&claim, &proof));
+        let_assert!(VerificationError::AuxiliaryCodewordAuthenticationFailure = err);
+    }
+
+    #[test]
+    fn verifying_proof_with_tampered_quotient_segments_fails_with_authentication_error() {
+        let (stark, claim, proof) = claim_and_proof_to_tamper_with();
+        let proof = tamper_with_proof(&proof, |item| {
+            if let ProofItem::QuotientSegmentsElements(segments) = item {
+                segments[0][0] += xfe!(1);
+            }
+        });
+
+        let_assert!(Err(err) = stark.verify(&claim, &proof));
+        let_assert!(VerificationError::QuotientCodewordAuthenticationFailure = err);
+    }
+
     #[test]
     fn prove_and_verify_simple_program() {
         prove_and_verify(
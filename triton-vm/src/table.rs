@@ -1,9 +1,30 @@
+use std::any::type_name;
+use std::ops::Range;
+
+use air::cross_table_argument::GrandCrossTableArg;
+use air::table::cascade::CascadeTable;
+use air::table::hash::HashTable;
+use air::table::jump_stack::JumpStackTable;
+use air::table::lookup::LookupTable;
+use air::table::op_stack::OpStackTable;
+use air::table::processor::ProcessorTable;
+use air::table::program::ProgramTable;
+use air::table::ram::RamTable;
+use air::table::u32::U32Table;
+use air::table_column::MasterMainColumn;
 use air::AIR;
+use constraint_circuit::ConstraintCircuitBuilder;
+use constraint_circuit::ConstraintCircuitMonad;
+use constraint_circuit::InputIndicator;
+use itertools::Itertools;
+use ndarray::s;
 use ndarray::ArrayView2;
 use ndarray::ArrayViewMut2;
+use num_traits::Zero;
 use strum::Display;
 use strum::EnumCount;
 use strum::EnumIter;
+use strum::IntoEnumIterator;
 use twenty_first::prelude::*;
 
 use crate::aet::AlgebraicExecutionTrace;
@@ -78,6 +99,159 @@ pub type AuxiliaryRow = [XFieldElement; MasterAuxTable::NUM_COLUMNS];
 /// See also [`NUM_QUOTIENT_SEGMENTS`].
 pub type QuotientSegments = [XFieldElement; NUM_QUOTIENT_SEGMENTS];
 
+/// Summary of the values in one column of a filled main table. See [`column_statistics`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ColumnStatistics<C> {
+    pub column: C,
+
+    /// The smallest value in the column, compared by canonical representation.
+    /// Zero if the table has no rows.
+    pub min: BFieldElement,
+
+    /// The largest value in the column, compared by canonical representation.
+    /// Zero if the table has no rows.
+    pub max: BFieldElement,
+
+    pub num_distinct_values: usize,
+}
+
+/// The minimum, maximum, and number of distinct values of every column in the given main
+/// table of the [`AIR`] `A`. Helps spotting anomalies in generated traces, like a column
+/// that is never set.
+pub fn column_statistics<A>(
+    main_table: ArrayView2<BFieldElement>,
+) -> Vec<ColumnStatistics<A::MainColumn>>
+where
+    A: AIR,
+    A::MainColumn: IntoEnumIterator,
+{
+    assert_eq!(A::MainColumn::COUNT, main_table.ncols());
+
+    A::MainColumn::iter()
+        .map(|column| {
+            let values = main_table.column(column.main_index());
+            let (min, max) = values
+                .iter()
+                .minmax_by_key(|value| value.value())
+                .into_option()
+                .map(|(&min, &max)| (min, max))
+                .unwrap_or_default();
+            let num_distinct_values = values.iter().unique().count();
+
+            ColumnStatistics {
+                column,
+                min,
+                max,
+                num_distinct_values,
+            }
+        })
+        .collect()
+}
+
+/// Evaluate all [AIR](air) constraints of all tables on the given trace tables.
+/// Helps when developing new constraints. Considerably slower than proving.
+///
+/// # Panics
+///
+/// Panics at the first violated constraint, naming the table, the constraint, and
+/// the row it is violated on.
+pub(crate) fn assert_all_constraints_hold<'a>(
+    main_table: ArrayView2<'a, BFieldElement>,
+    aux_table: ArrayView2<'a, XFieldElement>,
+    challenges: &'a Challenges,
+) {
+    let tables = (main_table, aux_table, challenges);
+    assert_constraints_hold::<ProgramTable>(tables);
+    assert_constraints_hold::<ProcessorTable>(tables);
+    assert_constraints_hold::<OpStackTable>(tables);
+    assert_constraints_hold::<RamTable>(tables);
+    assert_constraints_hold::<JumpStackTable>(tables);
+    assert_constraints_hold::<HashTable>(tables);
+    assert_constraints_hold::<CascadeTable>(tables);
+    assert_constraints_hold::<LookupTable>(tables);
+    assert_constraints_hold::<U32Table>(tables);
+
+    // The grand cross-table argument only has terminal constraints.
+    let builder = ConstraintCircuitBuilder::new();
+    let terminal = GrandCrossTableArg::terminal_constraints(&builder);
+    let last_row = main_table.nrows() - 1;
+    let table = "GrandCrossTableArg";
+    let rows = last_row..last_row + 1;
+    assert_hold(table, ConstraintType::Terminal, terminal, rows, tables);
+}
+
+type TraceTablesAndChallenges<'a> = (
+    ArrayView2<'a, BFieldElement>,
+    ArrayView2<'a, XFieldElement>,
+    &'a Challenges,
+);
+
+fn assert_constraints_hold<T: AIR>(tables: TraceTablesAndChallenges) {
+    let table = type_name::<T>().rsplit("::").next().unwrap_or_default();
+    let last_row = tables.0.nrows() - 1;
+
+    let builder = ConstraintCircuitBuilder::new();
+    let initial = T::initial_constraints(&builder);
+    assert_hold(table, ConstraintType::Initial, initial, 0..1, tables);
+
+    let builder = ConstraintCircuitBuilder::new();
+    let consistency = T::consistency_constraints(&builder);
+    let all_rows = 0..last_row + 1;
+    assert_hold(
+        table,
+        ConstraintType::Consistency,
+        consistency,
+        all_rows,
+        tables,
+    );
+
+    let builder = ConstraintCircuitBuilder::new();
+    let transition = T::transition_constraints(&builder);
+    assert_hold(
+        table,
+        ConstraintType::Transition,
+        transition,
+        0..last_row,
+        tables,
+    );
+
+    let builder = ConstraintCircuitBuilder::new();
+    let terminal = T::terminal_constraints(&builder);
+    assert_hold(
+        table,
+        ConstraintType::Terminal,
+        terminal,
+        last_row..last_row + 1,
+        tables,
+    );
+}
+
+fn assert_hold<II: InputIndicator>(
+    table: &str,
+    constraint_type: ConstraintType,
+    constraints: Vec<ConstraintCircuitMonad<II>>,
+    rows: Range<usize>,
+    (main_table, aux_table, challenges): TraceTablesAndChallenges,
+) {
+    let num_rows_per_evaluation = match constraint_type {
+        ConstraintType::Transition => 2,
+        _ => 1,
+    };
+    let constraints = constraints.iter().map(|c| c.consume()).collect_vec();
+    for row_idx in rows {
+        let rows = s![row_idx..row_idx + num_rows_per_evaluation, ..];
+        let main_rows = main_table.slice(rows);
+        let aux_rows = aux_table.slice(rows);
+        for (constraint_idx, constraint) in constraints.iter().enumerate() {
+            let evaluation = constraint.evaluate(main_rows, aux_rows, &challenges.challenges);
+            assert!(
+                evaluation.is_zero(),
+                "{table}: {constraint_type} constraint {constraint_idx} is violated on row {row_idx}."
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -109,6 +283,7 @@ mod tests {
     use air::table::PROGRAM_TABLE_END;
     use air::table::RAM_TABLE_END;
     use air::table::U32_TABLE_END;
+    use air::table_column::OpStackMainColumn;
     use constraint_circuit::BinOp;
     use constraint_circuit::CircuitExpression;
     use constraint_circuit::ConstraintCircuit;
@@ -458,4 +633,38 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn column_statistics_of_op_stack_table_fixture() {
+        let (main_table, _) = crate::table::op_stack::tests::op_stack_table_fixture();
+        let statistics = column_statistics::<OpStackTable>(main_table.view());
+        assert_eq!(OpStackMainColumn::COUNT, statistics.len());
+
+        let statistics_of = |column| *statistics.iter().find(|s| s.column == column).unwrap();
+        let clk = statistics_of(OpStackMainColumn::CLK);
+        assert_eq!(
+            (bfe!(0), bfe!(4), 5),
+            (clk.min, clk.max, clk.num_distinct_values)
+        );
+
+        // padding rows set IB1 to a value different from 0 and 1
+        let ib1 = statistics_of(OpStackMainColumn::IB1ShrinkStack);
+        assert_eq!(bfe!(0), ib1.min);
+        assert_eq!(3, ib1.num_distinct_values);
+
+        let stack_pointer = statistics_of(OpStackMainColumn::StackPointer);
+        let osp = (stack_pointer.min, stack_pointer.max);
+        assert_eq!((bfe!(16), bfe!(17)), osp);
+        assert_eq!(2, stack_pointer.num_distinct_values);
+    }
+
+    #[test]
+    fn column_statistics_of_empty_table_are_zero() {
+        let main_table = Array2::zeros((0, OpStackMainColumn::COUNT));
+        for statistics in column_statistics::<OpStackTable>(main_table.view()) {
+            assert_eq!(bfe!(0), statistics.min);
+            assert_eq!(bfe!(0), statistics.max);
+            assert_eq!(0, statistics.num_distinct_values);
+        }
+    }
 }
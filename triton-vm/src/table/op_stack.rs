@@ -1,5 +1,6 @@
-use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::ops::Mul;
+use std::ops::MulAssign;
 use std::ops::Range;
 
 use air::challenge_id::ChallengeId;
@@ -9,29 +10,44 @@ use air::cross_table_argument::PermArg;
 use air::table::op_stack::OpStackTable;
 use air::table::op_stack::PADDING_VALUE;
 use air::table::TableId;
+use air::table::AUX_OP_STACK_TABLE_END;
+use air::table::AUX_OP_STACK_TABLE_START;
+use air::table::OP_STACK_TABLE_END;
+use air::table::OP_STACK_TABLE_START;
 use air::table_column::MasterAuxColumn;
 use air::table_column::MasterMainColumn;
 use air::table_column::OpStackAuxColumn;
+use air::AIR;
 use arbitrary::Arbitrary;
+use constraint_circuit::ConstraintCircuit;
+use constraint_circuit::ConstraintCircuitBuilder;
+use constraint_circuit::ConstraintCircuitMonad;
+use constraint_circuit::InputIndicator;
 use isa::op_stack::OpStackElement;
 use isa::op_stack::UnderflowIO;
 use itertools::Itertools;
-use ndarray::parallel::prelude::*;
 use ndarray::prelude::*;
+use num_traits::ConstOne;
+use rayon::prelude::*;
 use strum::EnumCount;
 use strum::IntoEnumIterator;
 use twenty_first::math::traits::FiniteField;
 use twenty_first::prelude::*;
 
 use crate::aet::AlgebraicExecutionTrace;
+use crate::arithmetic_domain::ArithmeticDomain;
 use crate::challenges::Challenges;
+use crate::config;
+use crate::error::IsolatedTableError;
+use crate::error::ProvingError;
 use crate::ndarray_helper::contiguous_column_slices;
 use crate::ndarray_helper::horizontal_multi_slice_mut;
 use crate::profiler::profiler;
+use crate::table::ConstraintType;
 use crate::table::TraceTable;
 
-type MainColumn = <OpStackTable as air::AIR>::MainColumn;
-type AuxColumn = <OpStackTable as air::AIR>::AuxColumn;
+type MainColumn = <OpStackTable as AIR>::MainColumn;
+type AuxColumn = <OpStackTable as AIR>::AuxColumn;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Arbitrary)]
 pub struct OpStackTableEntry {
@@ -106,26 +122,76 @@ fn auxiliary_column_running_product_permutation_argument(
     challenges: &Challenges,
 ) -> Array2<XFieldElement> {
     let perm_arg_indeterminate = challenges[ChallengeId::OpStackIndeterminate];
-
-    let mut running_product = PermArg::default_initial();
-    let mut auxiliary_column = Vec::with_capacity(main_table.nrows());
-    for row in main_table.rows() {
-        if row[MainColumn::IB1ShrinkStack.main_index()] != PADDING_VALUE {
-            let compressed_row = row[MainColumn::CLK.main_index()]
-                * challenges[ChallengeId::OpStackClkWeight]
-                + row[MainColumn::IB1ShrinkStack.main_index()]
-                    * challenges[ChallengeId::OpStackIb1Weight]
-                + row[MainColumn::StackPointer.main_index()]
-                    * challenges[ChallengeId::OpStackPointerWeight]
-                + row[MainColumn::FirstUnderflowElement.main_index()]
-                    * challenges[ChallengeId::OpStackFirstUnderflowElementWeight];
-            running_product *= perm_arg_indeterminate - compressed_row;
+    let factor = |row: ArrayView1<BFieldElement>| {
+        if row[MainColumn::IB1ShrinkStack.main_index()] == PADDING_VALUE {
+            return XFieldElement::ONE;
         }
-        auxiliary_column.push(running_product);
-    }
+        let compressed_row = row[MainColumn::CLK.main_index()]
+            * challenges[ChallengeId::OpStackClkWeight]
+            + row[MainColumn::IB1ShrinkStack.main_index()]
+                * challenges[ChallengeId::OpStackIb1Weight]
+            + row[MainColumn::StackPointer.main_index()]
+                * challenges[ChallengeId::OpStackPointerWeight]
+            + row[MainColumn::FirstUnderflowElement.main_index()]
+                * challenges[ChallengeId::OpStackFirstUnderflowElementWeight];
+        perm_arg_indeterminate - compressed_row
+    };
+
+    let auxiliary_column = if config::should_run_in_parallel(main_table.nrows()) {
+        let factors = main_table
+            .axis_iter(Axis(0))
+            .into_par_iter()
+            .map(factor)
+            .collect();
+        running_product(PermArg::default_initial(), factors)
+    } else {
+        main_table
+            .rows()
+            .into_iter()
+            .map(factor)
+            .scan(PermArg::default_initial(), |running_product, factor| {
+                *running_product *= factor;
+                Some(*running_product)
+            })
+            .collect()
+    };
     Array2::from_shape_vec((main_table.nrows(), 1), auxiliary_column).unwrap()
 }
 
+/// The running product of the given factors, starting from `initial`: the i-th element
+/// of the result is `initial` multiplied by the first i+1 factors.
+///
+/// Computed as a parallel prefix product. The factors are split into one chunk per
+/// thread. The chunks' products are computed in parallel and combined sequentially
+/// to get every chunk's starting value, from which the chunks' running products are
+/// again computed in parallel.
+fn running_product(initial: XFieldElement, mut factors: Vec<XFieldElement>) -> Vec<XFieldElement> {
+    let chunk_len = factors.len().div_ceil(rayon::current_num_threads()).max(1);
+    let chunk_products = factors
+        .par_chunks(chunk_len)
+        .map(|chunk| chunk.iter().fold(XFieldElement::ONE, |acc, &f| acc * f))
+        .collect::<Vec<_>>();
+    let chunk_initials = chunk_products
+        .into_iter()
+        .scan(initial, |running_product, chunk_product| {
+            let chunk_initial = *running_product;
+            *running_product *= chunk_product;
+            Some(chunk_initial)
+        })
+        .collect::<Vec<_>>();
+
+    factors
+        .par_chunks_mut(chunk_len)
+        .zip_eq(chunk_initials)
+        .for_each(|(chunk, mut running_product)| {
+            for factor in chunk {
+                running_product *= *factor;
+                *factor = running_product;
+            }
+        });
+    factors
+}
+
 fn auxiliary_column_clock_jump_diff_lookup_log_derivative(
     main_table: ArrayView2<BFieldElement>,
     challenges: &Challenges,
@@ -182,18 +248,19 @@ impl TraceTable for OpStackTable {
     ) -> Vec<BFieldElement> {
         let mut op_stack_table =
             op_stack_table.slice_mut(s![0..aet.height_of_table(TableId::OpStack), ..]);
-        let trace_iter = aet.op_stack_underflow_trace.rows().into_iter();
-
-        let sorted_rows =
-            trace_iter.sorted_by(|row_0, row_1| compare_rows(row_0.view(), row_1.view()));
-        for (row_index, row) in sorted_rows.enumerate() {
-            op_stack_table.row_mut(row_index).assign(&row);
-        }
+        fill_with_sorted_rows(
+            aet.op_stack_underflow_trace.view(),
+            op_stack_table.view_mut(),
+        );
 
         clock_jump_differences(op_stack_table.view())
     }
 
     fn pad(mut op_stack_table: ArrayViewMut2<BFieldElement>, op_stack_table_len: usize) {
+        if op_stack_table_len == op_stack_table.nrows() {
+            return;
+        }
+
         let last_row_index = op_stack_table_len.saturating_sub(1);
         let mut padding_row = op_stack_table.row(last_row_index).to_owned();
         padding_row[MainColumn::IB1ShrinkStack.main_index()] = PADDING_VALUE;
@@ -242,16 +309,363 @@ impl TraceTable for OpStackTable {
     }
 }
 
-fn compare_rows(row_0: ArrayView1<BFieldElement>, row_1: ArrayView1<BFieldElement>) -> Ordering {
-    let stack_pointer_0 = row_0[MainColumn::StackPointer.main_index()].value();
-    let stack_pointer_1 = row_1[MainColumn::StackPointer.main_index()].value();
-    let compare_stack_pointers = stack_pointer_0.cmp(&stack_pointer_1);
+/// Ensure that no row of the given op stack underflow trace has a stack pointer
+/// smaller than the number of op stack registers. The op stack registers are never
+/// part of the underflow, so such a row can only stem from a malformed trace.
+pub(crate) fn ensure_no_stack_pointer_below_register_count(
+    op_stack_underflow_trace: ArrayView2<BFieldElement>,
+) -> Result<(), ProvingError> {
+    let min_stack_pointer = u64::try_from(OpStackElement::COUNT).unwrap();
+    for row in op_stack_underflow_trace.rows() {
+        let stack_pointer = row[MainColumn::StackPointer.main_index()].value();
+        if stack_pointer < min_stack_pointer {
+            let clk = row[MainColumn::CLK.main_index()].value();
+            return Err(ProvingError::OpStackUnderflow { clk, stack_pointer });
+        }
+    }
+
+    Ok(())
+}
+
+/// The number of clock jump differences the given op stack table looks up in the
+/// Processor Table, _i.e._, the number of summands in its
+/// [clock jump difference lookup argument][lookup]. Equals the number of pairs of
+/// consecutive, non-padding rows with the same stack pointer.
+///
+/// [lookup]: OpStackAuxColumn::ClockJumpDifferenceLookupClientLogDerivative
+pub fn num_clock_jump_difference_lookups(op_stack_table: ArrayView2<BFieldElement>) -> usize {
+    let stack_pointer = |row: ArrayView1<BFieldElement>| row[MainColumn::StackPointer.main_index()];
+
+    op_stack_table
+        .rows()
+        .into_iter()
+        .tuple_windows()
+        .take_while(|&(_, next_row)| !is_padding_row(next_row))
+        .filter(|&(row, next_row)| stack_pointer(row) == stack_pointer(next_row))
+        .count()
+}
+
+/// Copy the rows of the given op stack underflow trace into the given, unpadded op
+/// stack table, sorted by stack pointer first and clock cycle second.
+///
+/// Both sorting and copying happen in parallel if the trace is
+/// [long enough](crate::config::overwrite_parallelism_threshold_to). Since every row
+/// of the underflow trace ends up in exactly one row of the op stack table,
+/// determining the sorted order first allows filling all rows independently of each
+/// other.
+///
+/// # Panics
+///
+/// Panics if the op stack table does not have as many rows as the underflow trace.
+pub fn fill_with_sorted_rows(
+    underflow_trace: ArrayView2<BFieldElement>,
+    mut op_stack_table: ArrayViewMut2<BFieldElement>,
+) {
+    assert_eq!(underflow_trace.nrows(), op_stack_table.nrows());
+
+    // Including the row index makes all keys unique. This allows using the faster
+    // unstable sort while retaining the order of rows with equal sort keys.
+    let mut sort_keys = underflow_trace
+        .rows()
+        .into_iter()
+        .map(sort_key)
+        .enumerate()
+        .map(|(index, key)| (key, index))
+        .collect_vec();
+    let fill_row = |(mut row, (_, index)): (ArrayViewMut1<_>, (_, usize))| {
+        row.assign(&underflow_trace.row(index))
+    };
+
+    if config::should_run_in_parallel(underflow_trace.nrows()) {
+        sort_keys.par_sort_unstable();
+        op_stack_table
+            .axis_iter_mut(Axis(0))
+            .into_par_iter()
+            .zip(sort_keys)
+            .for_each(fill_row);
+    } else {
+        sort_keys.sort_unstable();
+        op_stack_table
+            .axis_iter_mut(Axis(0))
+            .zip(sort_keys)
+            .for_each(fill_row);
+    }
+}
+
+/// Whether the given row of the op stack table is a padding row. Padding rows
+/// are copies of the last non-padding row with the
+/// [instruction bit 1](MainColumn::IB1ShrinkStack) set to [`PADDING_VALUE`].
+pub fn is_padding_row(row: ArrayView1<BFieldElement>) -> bool {
+    row[MainColumn::IB1ShrinkStack.main_index()] == PADDING_VALUE
+}
+
+/// For every row of the given op stack table, whether that row is a
+/// [padding row](is_padding_row). Useful for visualizing a table, where padding
+/// rows are otherwise indistinguishable from the last non-padding row.
+pub fn padding_rows(op_stack_table: ArrayView2<BFieldElement>) -> Vec<bool> {
+    op_stack_table
+        .rows()
+        .into_iter()
+        .map(is_padding_row)
+        .collect()
+}
+
+/// The maximum number of rows rendered by [`to_ascii_table`].
+pub const MAX_NUM_ASCII_TABLE_ROWS: usize = 32;
+
+/// Render the given op stack table as an aligned text grid, with one column per
+/// [main column](MainColumn) headed by the column's name. Only the first
+/// [`MAX_NUM_ASCII_TABLE_ROWS`] rows are rendered, followed by the number of omitted
+/// rows, if any. Intended for quick debugging, for example using `println!`.
+pub fn to_ascii_table(op_stack_table: ArrayView2<BFieldElement>) -> String {
+    assert_eq!(MainColumn::COUNT, op_stack_table.ncols());
+
+    let header = MainColumn::iter()
+        .map(|column| column.to_string())
+        .collect_vec();
+    let rows = op_stack_table
+        .rows()
+        .into_iter()
+        .take(MAX_NUM_ASCII_TABLE_ROWS)
+        .map(|row| row.iter().map(|element| element.to_string()).collect_vec())
+        .collect_vec();
+    let column_widths = (0..MainColumn::COUNT)
+        .map(|col| {
+            let cell_widths = rows.iter().map(|row| row[col].len());
+            cell_widths
+                .chain([header[col].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect_vec();
+
+    let render_row = |cells: &[String]| {
+        let cells = cells
+            .iter()
+            .zip(&column_widths)
+            .map(|(cell, &width)| format!("{cell:>width$}"))
+            .join(" | ");
+        format!("| {cells} |\n")
+    };
+    let separator = column_widths
+        .iter()
+        .map(|&width| "-".repeat(width))
+        .join("-|-");
+
+    let mut table = render_row(&header);
+    table.push_str(&format!("|-{separator}-|\n"));
+    for row in &rows {
+        table.push_str(&render_row(row));
+    }
+    let num_omitted_rows = op_stack_table
+        .nrows()
+        .saturating_sub(MAX_NUM_ASCII_TABLE_ROWS);
+    if num_omitted_rows > 0 {
+        table.push_str(&format!("… ({num_omitted_rows} more rows)\n"));
+    }
+
+    table
+}
+
+/// Pad the given, unpadded op stack table to the next power of two. The result has at least
+/// one row.
+pub fn pad_to_power_of_two(op_stack_table: ArrayView2<BFieldElement>) -> Array2<BFieldElement> {
+    let op_stack_table_len = op_stack_table.nrows();
+    let padded_height = op_stack_table_len.next_power_of_two();
+
+    let mut padded_table = Array2::zeros((padded_height, op_stack_table.ncols()));
+    padded_table
+        .slice_mut(s![..op_stack_table_len, ..])
+        .assign(&op_stack_table);
+    OpStackTable::pad(padded_table.view_mut(), op_stack_table_len);
+
+    padded_table
+}
+
+/// Prove and verify the Op Stack Table's AIR in isolation, _i.e._, without any other
+/// table. Helps to localize bugs in the Op Stack Table's constraints, and is a lot faster
+/// than proving an entire program.
+///
+/// Every constraint is evaluated on a low-degree extension of the given, padded trace.
+/// The result is divided by the constraint's zerofier and interpolated. The check passes
+/// if all such quotients are polynomials of the expected degree.
+///
+/// **Note**: Cross-table arguments are not checked. In particular, this does _not_ prove
+/// that the Op Stack Table is consistent with the Processor Table.
+pub fn prove_and_verify_in_isolation(
+    main_table: ArrayView2<BFieldElement>,
+    aux_table: ArrayView2<XFieldElement>,
+    challenges: &Challenges,
+) -> Result<(), IsolatedTableError> {
+    assert_eq!(MainColumn::COUNT, main_table.ncols());
+    assert_eq!(AuxColumn::COUNT, aux_table.ncols());
+    let height = main_table.nrows();
+    if aux_table.nrows() != height {
+        return Err(IsolatedTableError::TableHeightMismatch {
+            main_height: height,
+            aux_height: aux_table.nrows(),
+        });
+    }
+
+    let single_row_builder = ConstraintCircuitBuilder::new();
+    let dual_row_builder = ConstraintCircuitBuilder::new();
+    let initial = consume_all(OpStackTable::initial_constraints(&single_row_builder));
+    let consistency = consume_all(OpStackTable::consistency_constraints(&single_row_builder));
+    let transition = consume_all(OpStackTable::transition_constraints(&dual_row_builder));
+    let terminal = consume_all(OpStackTable::terminal_constraints(&single_row_builder));
+
+    let max_constraint_degree = initial
+        .iter()
+        .chain(&consistency)
+        .chain(&terminal)
+        .map(|constraint| constraint.degree())
+        .chain(transition.iter().map(|constraint| constraint.degree()))
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    let max_constraint_degree = usize::try_from(max_constraint_degree).unwrap();
+
+    let trace_domain = ArithmeticDomain::of_length(height)?;
+    let quotient_domain_len = (max_constraint_degree * height).next_power_of_two();
+    let quotient_domain =
+        ArithmeticDomain::of_length(quotient_domain_len)?.with_offset(BFieldElement::generator());
+    let table = IsolatedOpStackTable {
+        main_lde: low_degree_extend(main_table, trace_domain, quotient_domain),
+        aux_lde: low_degree_extend(aux_table, trace_domain, quotient_domain),
+        trace_domain,
+        quotient_domain,
+        challenges,
+    };
+
+    table.ensure_low_degree_quotients(ConstraintType::Initial, &initial)?;
+    table.ensure_low_degree_quotients(ConstraintType::Consistency, &consistency)?;
+    table.ensure_low_degree_quotients(ConstraintType::Transition, &transition)?;
+    table.ensure_low_degree_quotients(ConstraintType::Terminal, &terminal)?;
+
+    Ok(())
+}
+
+fn consume_all<II: InputIndicator>(
+    constraints: Vec<ConstraintCircuitMonad<II>>,
+) -> Vec<ConstraintCircuit<II>> {
+    constraints.into_iter().map(|c| c.consume()).collect()
+}
+
+fn low_degree_extend<FF>(
+    table: ArrayView2<FF>,
+    trace_domain: ArithmeticDomain,
+    quotient_domain: ArithmeticDomain,
+) -> Array2<FF>
+where
+    FF: FiniteField
+        + MulAssign<BFieldElement>
+        + Mul<BFieldElement, Output = FF>
+        + From<BFieldElement>
+        + 'static,
+{
+    let codewords = table
+        .columns()
+        .into_iter()
+        .map(|column| trace_domain.low_degree_extension(&column.to_vec(), quotient_domain))
+        .collect_vec();
+
+    Array2::from_shape_fn((quotient_domain.length, table.ncols()), |(row, column)| {
+        codewords[column][row]
+    })
+}
+
+/// The low-degree extension of an Op Stack Table, for checking its AIR in isolation.
+/// See [`prove_and_verify_in_isolation`].
+struct IsolatedOpStackTable<'a> {
+    main_lde: Array2<BFieldElement>,
+    aux_lde: Array2<XFieldElement>,
+    trace_domain: ArithmeticDomain,
+    quotient_domain: ArithmeticDomain,
+    challenges: &'a Challenges,
+}
+
+impl IsolatedOpStackTable<'_> {
+    /// Ensure that dividing each of the constraints by the corresponding zerofier results in
+    /// a polynomial of degree at most `constraint_degree · (height - 1) - zerofier_degree`.
+    fn ensure_low_degree_quotients<II: InputIndicator>(
+        &self,
+        constraint_type: ConstraintType,
+        constraints: &[ConstraintCircuit<II>],
+    ) -> Result<(), IsolatedTableError> {
+        let quotient_domain_len = self.quotient_domain.length;
+        let unit_distance = quotient_domain_len / self.trace_domain.length;
+
+        // The constraints refer to columns by their index in the master tables.
+        let mut main_rows = Array2::zeros((2, OP_STACK_TABLE_END));
+        let mut aux_rows = Array2::zeros((2, AUX_OP_STACK_TABLE_END));
+
+        let mut quotient_codewords =
+            vec![Vec::with_capacity(quotient_domain_len); constraints.len()];
+        for (row_index, x) in self.quotient_domain.domain_values().into_iter().enumerate() {
+            let next_row_index = (row_index + unit_distance) % quotient_domain_len;
+            for (buffer_row, lde_row) in [row_index, next_row_index].into_iter().enumerate() {
+                main_rows
+                    .slice_mut(s![buffer_row, OP_STACK_TABLE_START..])
+                    .assign(&self.main_lde.row(lde_row));
+                aux_rows
+                    .slice_mut(s![buffer_row, AUX_OP_STACK_TABLE_START..])
+                    .assign(&self.aux_lde.row(lde_row));
+            }
+
+            let zerofier_inverse = self.zerofier_inverse(constraint_type, x);
+            for (constraint, codeword) in constraints.iter().zip_eq(&mut quotient_codewords) {
+                let challenges = &self.challenges.challenges;
+                let evaluation = constraint.evaluate(main_rows.view(), aux_rows.view(), challenges);
+                codeword.push(evaluation * zerofier_inverse);
+            }
+        }
+
+        let height = isize::try_from(self.trace_domain.length).unwrap();
+        let zerofier_degree = match constraint_type {
+            ConstraintType::Initial | ConstraintType::Terminal => 1,
+            ConstraintType::Consistency => height,
+            ConstraintType::Transition => height - 1,
+        };
+        let constraints_and_codewords = constraints.iter().zip_eq(quotient_codewords);
+        for (constraint_index, (constraint, codeword)) in constraints_and_codewords.enumerate() {
+            let max_degree = constraint.degree() * (height - 1) - zerofier_degree;
+            let degree = self.quotient_domain.interpolate(&codeword).degree();
+            if degree > max_degree {
+                return Err(IsolatedTableError::QuotientDegreeTooHigh {
+                    constraint_type,
+                    constraint_index,
+                    degree,
+                    max_degree,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn zerofier_inverse(&self, constraint_type: ConstraintType, x: BFieldElement) -> BFieldElement {
+        let height = u64::try_from(self.trace_domain.length).unwrap();
+        let last_trace_domain_value = self.trace_domain.generator.inverse();
+        let consistency_zerofier = x.mod_pow(height) - bfe!(1);
+
+        match constraint_type {
+            ConstraintType::Initial => (x - bfe!(1)).inverse(),
+            ConstraintType::Consistency => consistency_zerofier.inverse(),
+            ConstraintType::Transition => {
+                (x - last_trace_domain_value) * consistency_zerofier.inverse()
+            }
+            ConstraintType::Terminal => (x - last_trace_domain_value).inverse(),
+        }
+    }
+}
 
-    let clk_0 = row_0[MainColumn::CLK.main_index()].value();
-    let clk_1 = row_1[MainColumn::CLK.main_index()].value();
-    let compare_clocks = clk_0.cmp(&clk_1);
+/// The key by which the rows of the op stack table are sorted: stack pointer first,
+/// clock cycle second.
+fn sort_key(row: ArrayView1<BFieldElement>) -> (u64, u64) {
+    let stack_pointer = row[MainColumn::StackPointer.main_index()].value();
+    let clk = row[MainColumn::CLK.main_index()].value();
 
-    compare_stack_pointers.then(compare_clocks)
+    (stack_pointer, clk)
 }
 
 fn clock_jump_differences(op_stack_table: ArrayView2<BFieldElement>) -> Vec<BFieldElement> {
@@ -274,15 +688,145 @@ fn clock_jump_differences(op_stack_table: ArrayView2<BFieldElement>) -> Vec<BFie
 #[cfg(test)]
 pub(crate) mod tests {
     use assert2::assert;
+    use assert2::let_assert;
+    use constraint_circuit::CircuitExpression;
+    use constraint_circuit::DualRowIndicator;
     use isa::op_stack::OpStackElement;
     use itertools::Itertools;
+    use num_traits::Zero;
     use proptest::collection::vec;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use test_strategy::proptest;
 
+    use crate::triton_program;
+    use crate::vm::VM;
+
     use super::*;
 
+    /// The serial computation of the permutation argument's running product, as a
+    /// reference for the parallel implementation.
+    fn serial_running_product_permutation_argument(
+        main_table: ArrayView2<BFieldElement>,
+        challenges: &Challenges,
+    ) -> Vec<XFieldElement> {
+        let perm_arg_indeterminate = challenges[ChallengeId::OpStackIndeterminate];
+
+        let mut running_product = PermArg::default_initial();
+        let mut auxiliary_column = Vec::with_capacity(main_table.nrows());
+        for row in main_table.rows() {
+            if row[MainColumn::IB1ShrinkStack.main_index()] != PADDING_VALUE {
+                let compressed_row = row[MainColumn::CLK.main_index()]
+                    * challenges[ChallengeId::OpStackClkWeight]
+                    + row[MainColumn::IB1ShrinkStack.main_index()]
+                        * challenges[ChallengeId::OpStackIb1Weight]
+                    + row[MainColumn::StackPointer.main_index()]
+                        * challenges[ChallengeId::OpStackPointerWeight]
+                    + row[MainColumn::FirstUnderflowElement.main_index()]
+                        * challenges[ChallengeId::OpStackFirstUnderflowElementWeight];
+                running_product *= perm_arg_indeterminate - compressed_row;
+            }
+            auxiliary_column.push(running_product);
+        }
+        auxiliary_column
+    }
+
+    /// A small, filled, padded, and extended op stack table, resulting from running the
+    /// program `push 1 push 2 pop 1 push 3 pop 2 halt`. The auxiliary table is
+    /// computed using [`Challenges::default()`].
+    ///
+    /// The 6 non-padding rows exercise two different op stack pointers, stack shrinks
+    /// as well as stack growths, and a clock jump difference greater than 1:
+    ///
+    /// | clk | ib1 | stack pointer |
+    /// |----:|----:|--------------:|
+    /// |   0 |   0 |            16 |
+    /// |   4 |   1 |            16 |
+    /// |   1 |   0 |            17 |
+    /// |   2 |   1 |            17 |
+    /// |   3 |   0 |            17 |
+    /// |   4 |   1 |            17 |
+    ///
+    /// The remaining 2 rows are padding rows.
+    pub(crate) fn op_stack_table_fixture() -> (Array2<BFieldElement>, Array2<XFieldElement>) {
+        let program = triton_program!(push 1 push 2 pop 1 push 3 pop 2 halt);
+        let (aet, _) = VM::trace_execution(program, [].into(), [].into()).unwrap();
+
+        let op_stack_table_len = aet.height_of_table(TableId::OpStack);
+        let mut main_table = Array2::zeros((op_stack_table_len, MainColumn::COUNT));
+        OpStackTable::fill(main_table.view_mut(), &aet, ());
+        let main_table = pad_to_power_of_two(main_table.view());
+
+        let mut aux_table = Array2::zeros((main_table.nrows(), AuxColumn::COUNT));
+        let challenges = Challenges::default();
+        OpStackTable::extend(main_table.view(), aux_table.view_mut(), &challenges);
+
+        (main_table, aux_table)
+    }
+
+    #[proptest(cases = 20)]
+    fn parallel_and_serial_permutation_argument_running_products_are_identical(
+        #[strategy(0_usize..300)] num_rows: usize,
+        #[strategy(vec(arb(), #num_rows * MainColumn::COUNT))] elements: Vec<BFieldElement>,
+        #[strategy(vec(any::<bool>(), #num_rows))] is_padding_row: Vec<bool>,
+        #[strategy(1_usize..8)] num_threads: usize,
+        #[strategy(arb())] challenges: Challenges,
+    ) {
+        let mut main_table = Array2::from_shape_vec((num_rows, MainColumn::COUNT), elements)?;
+        let padding_rows = is_padding_row
+            .into_iter()
+            .positions(|is_padding| is_padding);
+        for row_index in padding_rows {
+            main_table[[row_index, MainColumn::IB1ShrinkStack.main_index()]] = PADDING_VALUE;
+        }
+
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()?;
+        // The configuration is thread-local, so it must be set on the thread doing the work.
+        let auxiliary_column_with_parallelism_threshold = |threshold| {
+            thread_pool.install(|| {
+                crate::config::overwrite_parallelism_threshold_to(threshold);
+                auxiliary_column_running_product_permutation_argument(
+                    main_table.view(),
+                    &challenges,
+                )
+            })
+        };
+        let serial = serial_running_product_permutation_argument(main_table.view(), &challenges);
+
+        let always_parallel = auxiliary_column_with_parallelism_threshold(0);
+        prop_assert_eq!(&serial, &always_parallel.column(0).to_vec());
+
+        let never_parallel = auxiliary_column_with_parallelism_threshold(usize::MAX);
+        prop_assert_eq!(serial, never_parallel.column(0).to_vec());
+    }
+
+    #[test]
+    fn running_product_of_no_factors_is_empty() {
+        assert!(running_product(xfe!(42), vec![]).is_empty());
+    }
+
+    #[test]
+    fn op_stack_table_fixture_has_documented_contents() {
+        let (main_table, aux_table) = op_stack_table_fixture();
+        assert!(8 == main_table.nrows());
+        assert!(8 == aux_table.nrows());
+
+        let column = |column: MainColumn| main_table.column(column.main_index()).to_vec();
+        let clk = column(MainColumn::CLK);
+        let ib1 = column(MainColumn::IB1ShrinkStack);
+        let stack_pointer = column(MainColumn::StackPointer);
+
+        assert!(bfe_vec![0, 4, 1, 2, 3, 4] == clk[..6]);
+        assert!(bfe_vec![0, 1, 0, 1, 0, 1] == ib1[..6]);
+        assert!(bfe_vec![16, 16, 17, 17, 17, 17] == stack_pointer[..6]);
+        assert!(ib1[6..].iter().all(|&ib1| ib1 == PADDING_VALUE));
+
+        let clock_jump_differences = clock_jump_differences(main_table.slice(s![..6, ..]));
+        assert!(bfe_vec![4, 1, 1, 1] == clock_jump_differences);
+    }
+
     #[proptest]
     fn op_stack_table_entry_either_shrinks_stack_or_grows_stack(
         #[strategy(arb())] entry: OpStackTableEntry,
@@ -367,7 +911,7 @@ pub(crate) mod tests {
         row_1[MainColumn::CLK.main_index()] = clk.into();
 
         let stack_pointer_comparison = stack_pointer_0.cmp(&stack_pointer_1);
-        let row_comparison = compare_rows(row_0.view(), row_1.view());
+        let row_comparison = sort_key(row_0.view()).cmp(&sort_key(row_1.view()));
 
         prop_assert_eq!(stack_pointer_comparison, row_comparison);
     }
@@ -387,8 +931,354 @@ pub(crate) mod tests {
         row_1[MainColumn::CLK.main_index()] = clk_1.into();
 
         let clk_comparison = clk_0.cmp(&clk_1);
-        let row_comparison = compare_rows(row_0.view(), row_1.view());
+        let row_comparison = sort_key(row_0.view()).cmp(&sort_key(row_1.view()));
 
         prop_assert_eq!(clk_comparison, row_comparison);
     }
+
+    /// An underflow trace with the given stack pointers and clock cycles. Every row is
+    /// unique, even if stack pointers and clock cycles repeat.
+    fn underflow_trace_with_sort_keys(
+        stack_pointers_and_clocks: Vec<(u64, u64)>,
+    ) -> Array2<BFieldElement> {
+        let num_rows = stack_pointers_and_clocks.len();
+        let mut underflow_trace = Array2::zeros((num_rows, MainColumn::COUNT));
+        for (row_index, (mut row, (stack_pointer, clk))) in underflow_trace
+            .rows_mut()
+            .into_iter()
+            .zip_eq(stack_pointers_and_clocks)
+            .enumerate()
+        {
+            row[MainColumn::StackPointer.main_index()] = bfe!(stack_pointer);
+            row[MainColumn::CLK.main_index()] = bfe!(clk);
+            row[MainColumn::FirstUnderflowElement.main_index()] = bfe!(row_index);
+        }
+
+        underflow_trace
+    }
+
+    fn fill_with_parallelism_threshold(
+        underflow_trace: ArrayView2<BFieldElement>,
+        threshold: usize,
+    ) -> Array2<BFieldElement> {
+        crate::config::overwrite_parallelism_threshold_to(threshold);
+        let mut op_stack_table = Array2::zeros(underflow_trace.raw_dim());
+        fill_with_sorted_rows(underflow_trace, op_stack_table.view_mut());
+        op_stack_table
+    }
+
+    #[proptest]
+    fn filling_in_parallel_matches_filling_serially(
+        #[strategy(vec((0_u64..8, 0_u64..32), 0..200))] stack_pointers_and_clocks: Vec<(u64, u64)>,
+    ) {
+        let underflow_trace = underflow_trace_with_sort_keys(stack_pointers_and_clocks);
+
+        let mut naively_filled_table = Array2::zeros(underflow_trace.raw_dim());
+        let sorted_rows = underflow_trace
+            .rows()
+            .into_iter()
+            .sorted_by_key(|row| sort_key(row.view()));
+        for (row_index, row) in sorted_rows.enumerate() {
+            naively_filled_table.row_mut(row_index).assign(&row);
+        }
+
+        let always_parallel = fill_with_parallelism_threshold(underflow_trace.view(), 0);
+        prop_assert_eq!(&naively_filled_table, &always_parallel);
+
+        let never_parallel = fill_with_parallelism_threshold(underflow_trace.view(), usize::MAX);
+        prop_assert_eq!(naively_filled_table, never_parallel);
+    }
+
+    #[test]
+    fn filling_gives_identical_results_across_parallelism_threshold() {
+        const THRESHOLD: usize = 32;
+
+        for num_rows in THRESHOLD - 1..=THRESHOLD + 1 {
+            let stack_pointers_and_clocks = (0..num_rows as u64)
+                .map(|i| (16 + (7 * i) % 5, i % 3))
+                .collect();
+            let underflow_trace = underflow_trace_with_sort_keys(stack_pointers_and_clocks);
+
+            let serially = fill_with_parallelism_threshold(underflow_trace.view(), usize::MAX);
+            let around_threshold =
+                fill_with_parallelism_threshold(underflow_trace.view(), THRESHOLD);
+            assert!(serially == around_threshold, "{num_rows} rows");
+        }
+    }
+
+    /// Whether the circuit reads the given input anywhere.
+    fn reads<II: InputIndicator>(circuit: &ConstraintCircuit<II>, input: II) -> bool {
+        match &circuit.expression {
+            CircuitExpression::Input(circuit_input) => *circuit_input == input,
+            CircuitExpression::BinOp(_, lhs, rhs) => {
+                reads(&lhs.borrow(), input) || reads(&rhs.borrow(), input)
+            }
+            _ => false,
+        }
+    }
+
+    /// Evaluate the transition constraint that requires the stack pointer to increase
+    /// by 1, the first underflow element to remain unchanged, or the next row to grow
+    /// the stack. It is the only main-table transition constraint that reads the
+    /// first underflow element. Rows are given as
+    /// `(stack_pointer, first_underflow_element, ib1)`.
+    fn evaluate_underflow_element_transition_constraint(
+        current_row: (u64, u64, u64),
+        next_row: (u64, u64, u64),
+    ) -> XFieldElement {
+        let mut main_rows = Array2::zeros((2, air::table::NUM_MAIN_COLUMNS));
+        for (mut row, (stack_pointer, first_underflow_element, ib1)) in main_rows
+            .rows_mut()
+            .into_iter()
+            .zip([current_row, next_row])
+        {
+            row[MainColumn::StackPointer.master_main_index()] = bfe!(stack_pointer);
+            row[MainColumn::FirstUnderflowElement.master_main_index()] =
+                bfe!(first_underflow_element);
+            row[MainColumn::IB1ShrinkStack.master_main_index()] = bfe!(ib1);
+        }
+        let aux_rows = Array2::zeros((2, air::table::NUM_AUX_COLUMNS));
+
+        let circuit_builder = ConstraintCircuitBuilder::new();
+        let next_first_underflow_element =
+            DualRowIndicator::NextMain(MainColumn::FirstUnderflowElement.master_main_index());
+        let constraints = OpStackTable::transition_constraints(&circuit_builder)
+            .into_iter()
+            .map(|constraint| constraint.consume())
+            .filter(|constraint| constraint.evaluates_to_base_element())
+            .filter(|constraint| reads(constraint, next_first_underflow_element))
+            .collect_vec();
+        let_assert!([constraint] = constraints.as_slice());
+        let challenges = Challenges::default();
+        constraint.evaluate(main_rows.view(), aux_rows.view(), &challenges.challenges)
+    }
+
+    #[test]
+    fn growing_stack_pointer_allows_changing_underflow_element() {
+        for next_ib1 in [0, 1] {
+            let evaluation =
+                evaluate_underflow_element_transition_constraint((16, 5, 0), (17, 9, next_ib1));
+            assert!(evaluation.is_zero(), "next ib1: {next_ib1}");
+        }
+    }
+
+    #[test]
+    fn shrinking_stack_with_constant_stack_pointer_keeps_underflow_element() {
+        let evaluation = evaluate_underflow_element_transition_constraint((16, 5, 0), (16, 5, 1));
+        assert!(evaluation.is_zero());
+    }
+
+    #[test]
+    fn growing_stack_with_constant_stack_pointer_allows_changing_underflow_element() {
+        let evaluation = evaluate_underflow_element_transition_constraint((16, 5, 1), (16, 9, 0));
+        assert!(evaluation.is_zero());
+    }
+
+    #[test]
+    fn shrinking_stack_with_constant_stack_pointer_and_changing_underflow_element_is_rejected() {
+        let evaluation = evaluate_underflow_element_transition_constraint((16, 5, 0), (16, 9, 1));
+        assert!(!evaluation.is_zero());
+    }
+
+    #[test]
+    fn padding_row_with_changing_underflow_element_is_rejected() {
+        let padding = PADDING_VALUE.value();
+        let evaluation =
+            evaluate_underflow_element_transition_constraint((16, 5, 1), (16, 9, padding));
+        assert!(!evaluation.is_zero());
+    }
+
+    #[test]
+    fn ascii_table_has_header_and_renders_rows() {
+        let (main_table, _) = op_stack_table_fixture();
+        let table = to_ascii_table(main_table.view());
+        let lines = table.lines().collect_vec();
+        assert!(2 + main_table.nrows() == lines.len());
+
+        let header = lines[0];
+        for column in MainColumn::iter() {
+            assert!(header.contains(&column.to_string()));
+        }
+        assert!(lines[1].starts_with("|-"));
+
+        let cells = lines[2].split('|').map(str::trim).collect_vec();
+        assert!(["0", "0", "16"] == cells[1..4]);
+        for line in &lines {
+            assert!(lines[0].len() == line.len());
+        }
+    }
+
+    #[test]
+    fn ascii_table_of_tall_table_is_truncated() {
+        let num_rows = MAX_NUM_ASCII_TABLE_ROWS + 10;
+        let main_table = Array2::zeros((num_rows, MainColumn::COUNT));
+        let table = to_ascii_table(main_table.view());
+        let lines = table.lines().collect_vec();
+        assert!(2 + MAX_NUM_ASCII_TABLE_ROWS + 1 == lines.len());
+        let_assert!(Some(last_line) = lines.last());
+        assert!(last_line.contains("10 more rows"));
+    }
+
+    /// The op stack table of a program that never underflows the op stack, like one
+    /// consisting only of `halt`, is empty before padding.
+    #[test]
+    fn empty_op_stack_table_can_be_padded_and_extended() {
+        let mut main_table = Array2::zeros((1, MainColumn::COUNT));
+        OpStackTable::pad(main_table.view_mut(), 0);
+
+        let padding_row = main_table.row(0);
+        let initial_stack_pointer = bfe!(OpStackElement::COUNT as u64);
+        assert!(PADDING_VALUE == padding_row[MainColumn::IB1ShrinkStack.main_index()]);
+        assert!(initial_stack_pointer == padding_row[MainColumn::StackPointer.main_index()]);
+
+        let challenges = Challenges::default();
+        let mut aux_table = Array2::zeros((1, AuxColumn::COUNT));
+        OpStackTable::extend(main_table.view(), aux_table.view_mut(), &challenges);
+
+        let aux_row = aux_table.row(0);
+        let perm_arg = aux_row[AuxColumn::RunningProductPermArg.aux_index()];
+        let cjd_log_derivative =
+            aux_row[AuxColumn::ClockJumpDifferenceLookupClientLogDerivative.aux_index()];
+        assert!(PermArg::default_initial() == perm_arg);
+        assert!(LookupArg::default_initial() == cjd_log_derivative);
+    }
+
+    #[test]
+    fn op_stack_table_fixture_satisfies_air_in_isolation() {
+        let (main_table, aux_table) = op_stack_table_fixture();
+        let challenges = Challenges::default();
+        let verdict =
+            prove_and_verify_in_isolation(main_table.view(), aux_table.view(), &challenges);
+        assert!(let Ok(()) = verdict);
+    }
+
+    #[test]
+    fn tampered_op_stack_table_violates_air_in_isolation() {
+        let (mut main_table, aux_table) = op_stack_table_fixture();
+        main_table[[1, MainColumn::FirstUnderflowElement.main_index()]] += bfe!(1);
+
+        let challenges = Challenges::default();
+        let verdict =
+            prove_and_verify_in_isolation(main_table.view(), aux_table.view(), &challenges);
+        let_assert!(Err(IsolatedTableError::QuotientDegreeTooHigh { .. }) = verdict);
+    }
+
+    #[test]
+    fn op_stack_table_with_inconsistent_heights_cannot_be_checked_in_isolation() {
+        let (main_table, aux_table) = op_stack_table_fixture();
+        let aux_table = aux_table.slice(s![..4, ..]);
+
+        let challenges = Challenges::default();
+        let verdict = prove_and_verify_in_isolation(main_table.view(), aux_table, &challenges);
+        let_assert!(Err(IsolatedTableError::TableHeightMismatch { .. }) = verdict);
+    }
+
+    #[test]
+    fn number_of_clock_jump_difference_lookups_of_fixture_is_as_documented() {
+        let (main_table, _) = op_stack_table_fixture();
+        assert!(4 == num_clock_jump_difference_lookups(main_table.view()));
+    }
+
+    #[test]
+    fn padding_rows_do_not_contribute_clock_jump_difference_lookups() {
+        let (main_table, _) = op_stack_table_fixture();
+        let unpadded_table = main_table.slice(s![..6, ..]);
+        let num_lookups = num_clock_jump_difference_lookups(unpadded_table);
+        assert!(clock_jump_differences(unpadded_table).len() == num_lookups);
+        assert!(num_lookups == num_clock_jump_difference_lookups(main_table.view()));
+    }
+
+    #[test]
+    fn empty_op_stack_table_has_no_clock_jump_difference_lookups() {
+        let main_table = Array2::zeros((0, MainColumn::COUNT));
+        assert!(0 == num_clock_jump_difference_lookups(main_table.view()));
+    }
+
+    #[test]
+    fn only_padding_rows_of_fixture_are_marked_as_padding() {
+        let (main_table, _) = op_stack_table_fixture();
+        let padding_rows = padding_rows(main_table.view());
+        assert!([false; 6] == padding_rows[..6]);
+        assert!([true; 2] == padding_rows[6..]);
+    }
+
+    #[test]
+    fn padding_an_op_stack_table_marks_exactly_the_new_rows_as_padding() {
+        let (main_table, _) = op_stack_table_fixture();
+        let unpadded_table = main_table.slice(s![..5, ..]);
+        let padded_table = pad_to_power_of_two(unpadded_table);
+
+        let padding_rows = padding_rows(padded_table.view());
+        assert!(8 == padding_rows.len());
+        assert!(padding_rows
+            .iter()
+            .positions(|&is_padding| is_padding)
+            .eq(5..8));
+    }
+
+    #[test]
+    fn padding_already_padded_op_stack_table_is_a_no_op() {
+        let (mut main_table, _) = op_stack_table_fixture();
+        let original_table = main_table.clone();
+
+        let height = main_table.nrows();
+        OpStackTable::pad(main_table.view_mut(), height);
+        assert!(original_table == main_table);
+    }
+
+    #[test]
+    fn empty_op_stack_table_without_rows_can_be_padded() {
+        let mut main_table = Array2::zeros((0, MainColumn::COUNT));
+        OpStackTable::pad(main_table.view_mut(), 0);
+        assert!(0 == main_table.nrows());
+    }
+
+    #[test]
+    fn op_stack_table_is_padded_to_next_power_of_two() {
+        let main_table = Array2::from_shape_fn((5, MainColumn::COUNT), |(r, c)| {
+            bfe!((r * MainColumn::COUNT + c) as u64)
+        });
+        let padded_table = pad_to_power_of_two(main_table.view());
+
+        assert!(8 == padded_table.nrows());
+        assert!(main_table == padded_table.slice(s![..5, ..]));
+
+        let mut expected_padding_row = main_table.row(4).to_owned();
+        expected_padding_row[MainColumn::IB1ShrinkStack.main_index()] = PADDING_VALUE;
+        for padding_row in padded_table.slice(s![5.., ..]).rows() {
+            assert!(expected_padding_row == padding_row);
+        }
+    }
+
+    #[test]
+    fn empty_op_stack_table_is_padded_to_one_row() {
+        let main_table = Array2::zeros((0, MainColumn::COUNT));
+        let padded_table = pad_to_power_of_two(main_table.view());
+        assert!(1 == padded_table.nrows());
+    }
+
+    #[test]
+    fn underflow_trace_of_honest_execution_has_no_stack_pointer_below_register_count() {
+        let program = triton_program!(push 1 push 2 pop 1 push 3 pop 2 halt);
+        let (aet, _) = VM::trace_execution(program, [].into(), [].into()).unwrap();
+        let underflow_trace = aet.op_stack_underflow_trace.view();
+        assert!(let Ok(()) = ensure_no_stack_pointer_below_register_count(underflow_trace));
+    }
+
+    #[test]
+    fn stack_pointer_below_register_count_is_reported_with_clock_cycle() {
+        let (mut main_table, _) = op_stack_table_fixture();
+        main_table[[3, MainColumn::StackPointer.main_index()]] = bfe!(15);
+        let clk = main_table[[3, MainColumn::CLK.main_index()]].value();
+
+        let validation = ensure_no_stack_pointer_below_register_count(main_table.view());
+        let_assert!(
+            Err(ProvingError::OpStackUnderflow {
+                clk: err_clk,
+                stack_pointer
+            }) = validation
+        );
+        assert!(clk == err_clk);
+        assert!(15 == stack_pointer);
+    }
 }
@@ -139,6 +139,66 @@ impl TraceTable for RamTable {
     }
 }
 
+/// A read from RAM that does not return the value of the most recent access to the
+/// same address. See [`ram_inconsistencies`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
+pub struct RamInconsistency {
+    pub ram_pointer: BFieldElement,
+
+    /// The clock cycle of the offending read.
+    pub clk: u32,
+
+    /// The value returned by the offending read.
+    pub read_value: BFieldElement,
+
+    /// The clock cycle of the most recent preceding access to the same address.
+    pub previous_clk: u32,
+
+    /// The value of the most recent preceding access to the same address.
+    pub expected_value: BFieldElement,
+}
+
+/// All reads in the [RAM trace](AlgebraicExecutionTrace::ram_trace) that do not return
+/// the value of the most recent access to the same address. The RAM Table's
+/// constraints enforce this memory consistency, so a non-empty result indicates a bug
+/// in the trace or in the VM that produced it.
+///
+/// The first access to any address is never inconsistent: the initial content of RAM
+/// is arbitrary.
+pub fn ram_inconsistencies(aet: &AlgebraicExecutionTrace) -> Vec<RamInconsistency> {
+    let mut inconsistencies = vec![];
+    let mut previous_access: Option<(BFieldElement, u32, BFieldElement)> = None;
+
+    // sorting is stable; accesses within the same cycle keep their order
+    let sorted_rows = aet
+        .ram_trace
+        .rows()
+        .into_iter()
+        .sorted_by(|row_0, row_1| compare_rows(row_0.view(), row_1.view()));
+    for row in sorted_rows {
+        let ram_pointer = row[MainColumn::RamPointer.main_index()];
+        let clk = u32::try_from(row[MainColumn::CLK.main_index()].value()).unwrap();
+        let ram_value = row[MainColumn::RamValue.main_index()];
+        let is_read =
+            row[MainColumn::InstructionType.main_index()] == air::table::ram::INSTRUCTION_TYPE_READ;
+
+        if let Some((previous_pointer, previous_clk, expected_value)) = previous_access {
+            if is_read && previous_pointer == ram_pointer && expected_value != ram_value {
+                inconsistencies.push(RamInconsistency {
+                    ram_pointer,
+                    clk,
+                    read_value: ram_value,
+                    previous_clk,
+                    expected_value,
+                });
+            }
+        }
+        previous_access = Some((ram_pointer, clk, ram_value));
+    }
+
+    inconsistencies
+}
+
 fn compare_rows(row_0: ArrayView1<BFieldElement>, row_1: ArrayView1<BFieldElement>) -> Ordering {
     let ram_pointer_0 = row_0[MainColumn::RamPointer.main_index()].value();
     let ram_pointer_1 = row_1[MainColumn::RamPointer.main_index()].value();
@@ -396,10 +456,14 @@ fn auxiliary_column_clock_jump_difference_lookup_log_derivative(
 
 #[cfg(test)]
 pub(crate) mod tests {
+    use isa::triton_program;
     use proptest::prelude::*;
     use proptest_arbitrary_interop::arb;
     use test_strategy::proptest;
 
+    use crate::vm::NonDeterminism;
+    use crate::vm::VM;
+
     use super::*;
 
     #[proptest]
@@ -409,6 +473,50 @@ pub(crate) mod tests {
         ram_table_call.to_table_row();
     }
 
+    #[test]
+    fn correct_run_has_no_ram_inconsistencies() {
+        let program =
+            crate::example_programs::CALCULATE_NEW_MMR_PEAKS_FROM_APPEND_WITH_SAFE_LISTS.clone();
+        let (aet, _) = VM::trace_execution(program, [].into(), [].into()).unwrap();
+        assert!(aet.ram_trace.nrows() > 0);
+        assert_eq!(Vec::<RamInconsistency>::new(), ram_inconsistencies(&aet));
+    }
+
+    #[test]
+    fn tampered_read_is_reported_as_ram_inconsistency() {
+        let program = triton_program! {
+            push 42 push 7 write_mem 1 pop 1
+            push 7 read_mem 1 pop 2
+            halt
+        };
+        let (mut aet, _) = VM::trace_execution(program, [].into(), [].into()).unwrap();
+        assert_eq!(2, aet.ram_trace.nrows());
+
+        let read_row_index = 1;
+        let read_row = aet.ram_trace.row(read_row_index).to_owned();
+        let is_read = read_row[MainColumn::InstructionType.main_index()]
+            == air::table::ram::INSTRUCTION_TYPE_READ;
+        assert!(is_read);
+        aet.ram_trace[[read_row_index, MainColumn::RamValue.main_index()]] = bfe!(43);
+
+        let expected_inconsistency = RamInconsistency {
+            ram_pointer: bfe!(7),
+            clk: 5,
+            read_value: bfe!(43),
+            previous_clk: 2,
+            expected_value: bfe!(42),
+        };
+        assert_eq!(vec![expected_inconsistency], ram_inconsistencies(&aet));
+    }
+
+    #[test]
+    fn first_read_of_an_address_is_never_inconsistent() {
+        let program = triton_program!(push 7 read_mem 1 pop 2 halt);
+        let non_determinism = NonDeterminism::default().with_ram([(bfe!(7), bfe!(42))]);
+        let (aet, _) = VM::trace_execution(program, [].into(), non_determinism).unwrap();
+        assert_eq!(Vec::<RamInconsistency>::new(), ram_inconsistencies(&aet));
+    }
+
     #[test]
     fn bezout_coefficient_polynomials_of_empty_ram_table_are_default() {
         let (a, b) = bezout_coefficient_polynomials_coefficients(&[]);
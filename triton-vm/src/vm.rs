@@ -1,3 +1,5 @@
+use std::array;
+use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::fmt::Display;
@@ -5,6 +7,9 @@ use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
 use std::ops::Deref;
 use std::ops::Range;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Instant;
 
 use air::table::hash::PermutationTrace;
 use air::table::processor::NUM_HELPER_VARIABLE_REGISTERS;
@@ -14,7 +19,10 @@ use arbitrary::Arbitrary;
 use isa::error::AssertionError;
 use isa::error::InstructionError;
 use isa::error::OpStackError;
+use isa::instruction::AnInstruction;
 use isa::instruction::Instruction;
+use isa::instruction::LabelledInstruction;
+use isa::instruction::ALL_INSTRUCTIONS;
 use isa::op_stack::NumberOfWords;
 use isa::op_stack::OpStack;
 use isa::op_stack::OpStackElement;
@@ -25,15 +33,21 @@ use ndarray::Array1;
 use num_traits::ConstOne;
 use num_traits::ConstZero;
 use num_traits::Zero;
+use rand::Rng;
+use rand_chacha::ChaCha12Rng;
+use rand_core::SeedableRng;
 use serde::Deserialize;
 use serde::Serialize;
 use strum::EnumCount;
+use strum::IntoEnumIterator;
+use twenty_first::error::ParseBFieldElementError;
 use twenty_first::math::x_field_element::EXTENSION_DEGREE;
 use twenty_first::prelude::*;
 use twenty_first::util_types::sponge;
 
 use crate::aet::AlgebraicExecutionTrace;
 use crate::error::VMError;
+use crate::execution_trace_profiler::CostModel;
 use crate::execution_trace_profiler::ExecutionTraceProfile;
 use crate::execution_trace_profiler::ExecutionTraceProfiler;
 use crate::profiler::profiler;
@@ -50,15 +64,29 @@ pub struct VM;
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Arbitrary)]
 pub struct VMState {
     /// The **program memory** stores the instructions (and their arguments) of the program
-    /// currently being executed by Triton VM. It is read-only.
-    pub program: Program,
+    /// currently being executed by Triton VM. It is read-only, which is why it is
+    /// shared between clones of a `VMState`: cloning only copies the execution state.
+    pub program: Arc<Program>,
+
+    /// For every address of the [program](Self::program), whether an instruction starts
+    /// there. Allows instruction `call` to reject misaligned destinations without walking
+    /// the program.
+    instruction_starts: Arc<[bool]>,
 
     /// A list of [`BFieldElement`]s the program can read from using instruction `read_io`.
     pub public_input: VecDeque<BFieldElement>,
 
+    /// The number of elements of [public input](Self::public_input) read so far.
+    /// See [`public_input_progress`](Self::public_input_progress).
+    num_public_input_elements_read: usize,
+
     /// A list of [`BFieldElement`]s the program can write to using instruction `write_io`.
     pub public_output: Vec<BFieldElement>,
 
+    /// The length of the [public output](Self::public_output) before the most recent
+    /// [step](Self::step). Used to determine the [delta](Self::public_output_delta).
+    public_output_len_before_step: usize,
+
     /// A list of [`BFieldElement`]s the program can read from using instruction `divine`.
     pub secret_individual_tokens: VecDeque<BFieldElement>,
 
@@ -68,6 +96,10 @@ pub struct VMState {
     /// The read-write **random-access memory** allows Triton VM to store arbitrary data.
     pub ram: HashMap<BFieldElement, BFieldElement>,
 
+    /// Address ranges of [RAM](Self::ram) that instruction `write_mem` must not write
+    /// to. See [`NonDeterminism::read_only_ram_regions`].
+    pub read_only_ram_regions: Vec<Range<u64>>,
+
     ram_calls: Vec<RamTableCall>,
 
     /// The **Op-stack memory** stores Triton VM's entire operational stack.
@@ -98,6 +130,11 @@ pub struct VMState {
 
     /// Indicates whether the terminating instruction `halt` has been executed.
     pub halting: bool,
+
+    /// For every element of the [op stack](Self::op_stack), indexed like
+    /// [`OpStack::stack`], the step that last modified it. Only tracked if
+    /// [enabled](Self::with_provenance_tracking).
+    op_stack_provenance: Option<Vec<Option<Provenance>>>,
 }
 
 /// A call from the main processor to one of the coprocessors, including the trace for that
@@ -141,12 +178,66 @@ impl VM {
         Ok(state.public_output)
     }
 
+    /// The number of clock cycles it takes to [`run`][run] the [`Program`] with the given
+    /// public input and non-determinism. This equals the height of the processor table
+    /// that [tracing][trace_execution] the execution would produce, but no
+    /// [`AlgebraicExecutionTrace`] is built. Useful for deciding whether to prove before
+    /// committing the resources to do so.
+    ///
+    /// Fails if and only if [`run`][run] fails, with the same error.
+    ///
+    /// [run]: Self::run
+    /// [trace_execution]: Self::trace_execution
+    pub fn clock_cycle_count(
+        program: Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> VMResult<u32> {
+        let mut state = VMState::new(program, public_input, non_determinism);
+        if let Err(err) = state.run() {
+            return Err(VMError::new(err, state));
+        }
+        Ok(state.cycle_count)
+    }
+
+    /// Run the [`Program`] and record a [`TraceRecord`] right before every clock
+    /// cycle, returning all records as a JSON array. Intended for external tooling.
+    ///
+    /// The number of records equals the [clock cycle count][count].
+    ///
+    /// [count]: Self::clock_cycle_count
+    pub fn trace_to_json(
+        program: Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> VMResult<String> {
+        let mut state = VMState::new(program, public_input, non_determinism);
+        let mut records = vec![];
+        while !state.halting {
+            let record = match TraceRecord::try_from(&state) {
+                Ok(record) => record,
+                Err(err) => return Err(VMError::new(err, state)),
+            };
+            records.push(record);
+            if let Err(err) = state.step() {
+                return Err(VMError::new(err, state));
+            }
+        }
+
+        let json = serde_json::to_string(&records).expect("trace records should serialize");
+        Ok(json)
+    }
+
     /// Trace the execution of a [`Program`]. That is, [`run`][run] the [`Program`] and additionally
     /// record that part of every encountered state that is necessary for proving correct execution.
     /// If execution  succeeds, returns
     /// 1. an [`AlgebraicExecutionTrace`], and
     /// 1. the output of the program.
     ///
+    /// The program is executed only once. The returned trace is exactly what the
+    /// [`Prover`](crate::prelude::Prover) consumes, and the returned output is identical to
+    /// the output of [`run`][run].
+    ///
     /// See also [`run`][run] and [`profile`][profile].
     ///
     /// [run]: Self::run
@@ -173,7 +264,7 @@ impl VM {
     pub fn trace_execution_of_state(
         mut state: VMState,
     ) -> VMResult<(AlgebraicExecutionTrace, VMState)> {
-        let mut aet = AlgebraicExecutionTrace::new(state.program.clone());
+        let mut aet = AlgebraicExecutionTrace::new(Program::clone(&state.program));
 
         while !state.halting {
             if let Err(err) = aet.record_state(&state) {
@@ -206,19 +297,80 @@ impl VM {
         public_input: PublicInput,
         non_determinism: NonDeterminism,
     ) -> VMResult<(Vec<BFieldElement>, ExecutionTraceProfile)> {
-        let mut profiler = ExecutionTraceProfiler::new(program.instructions.len());
+        let cost_model = CostModel::default();
+        Self::profile_with_cost_model(program, public_input, non_determinism, cost_model)
+    }
+
+    /// Like [`profile`][profile], but additionally computes the total
+    /// [weighted cost](ExecutionTraceProfile::weighted_cost) of the run according to the
+    /// given [`CostModel`].
+    ///
+    /// [profile]: Self::profile
+    pub fn profile_with_cost_model(
+        program: Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+        cost_model: CostModel,
+    ) -> VMResult<(Vec<BFieldElement>, ExecutionTraceProfile)> {
+        let measure_wall_time = false;
+        Self::profile_inner(
+            program,
+            public_input,
+            non_determinism,
+            cost_model,
+            measure_wall_time,
+        )
+    }
+
+    /// Like [`profile_with_cost_model`][profile], but additionally records the
+    /// approximate [wall-clock time](ExecutionTraceProfile::wall_time) spent executing
+    /// each kind of instruction. Measuring the time slows down execution somewhat.
+    ///
+    /// [profile]: Self::profile_with_cost_model
+    pub fn profile_with_wall_time(
+        program: Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+        cost_model: CostModel,
+    ) -> VMResult<(Vec<BFieldElement>, ExecutionTraceProfile)> {
+        let measure_wall_time = true;
+        Self::profile_inner(
+            program,
+            public_input,
+            non_determinism,
+            cost_model,
+            measure_wall_time,
+        )
+    }
+
+    fn profile_inner(
+        program: Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+        cost_model: CostModel,
+        measure_wall_time: bool,
+    ) -> VMResult<(Vec<BFieldElement>, ExecutionTraceProfile)> {
+        let mut profiler = ExecutionTraceProfiler::new(program.instructions.len(), cost_model);
         let mut state = VMState::new(program.clone(), public_input, non_determinism);
         let mut previous_jump_stack_len = state.jump_stack.len();
         while !state.halting {
-            if let Ok(Instruction::Call(address)) = state.current_instruction() {
+            let current_instruction = state.current_instruction();
+            if let Ok(Instruction::Call(address)) = current_instruction {
                 let label = program.label_for_address(address.value());
                 profiler.enter_span(label);
             }
 
+            let step_start = measure_wall_time.then(Instant::now);
             match state.step() {
                 Ok(calls) => profiler.handle_co_processor_calls(calls),
                 Err(err) => return Err(VMError::new(err, state)),
             };
+            if let Ok(instruction) = current_instruction {
+                profiler.handle_instruction(instruction);
+                if let Some(step_start) = step_start {
+                    profiler.handle_wall_time(instruction, step_start.elapsed());
+                }
+            }
 
             if state.jump_stack.len() < previous_jump_stack_len {
                 profiler.exit_span();
@@ -238,14 +390,19 @@ impl VMState {
         non_determinism: NonDeterminism,
     ) -> Self {
         let program_digest = program.hash();
+        let instruction_starts = program.instruction_starts().into();
 
         Self {
-            program,
+            program: Arc::new(program),
+            instruction_starts,
             public_input: public_input.individual_tokens.into(),
+            num_public_input_elements_read: 0,
             public_output: vec![],
+            public_output_len_before_step: 0,
             secret_individual_tokens: non_determinism.individual_tokens.into(),
             secret_digests: non_determinism.digests.into(),
             ram: non_determinism.ram,
+            read_only_ram_regions: non_determinism.read_only_ram_regions,
             ram_calls: vec![],
             op_stack: OpStack::new(program_digest),
             jump_stack: vec![],
@@ -253,6 +410,62 @@ impl VMState {
             instruction_pointer: 0,
             sponge: None,
             halting: false,
+            op_stack_provenance: None,
+        }
+    }
+
+    /// The number of elements of public input read so far, as well as the total
+    /// number of elements of public input, in this order. The total includes any
+    /// elements that were appended to the [public input](Self::public_input) after
+    /// creation of this `VMState`.
+    ///
+    /// A failed attempt to read more public input than is available does not
+    /// consume anything.
+    pub fn public_input_progress(&self) -> (usize, usize) {
+        let num_read = self.num_public_input_elements_read;
+        (num_read, num_read + self.public_input.len())
+    }
+
+    /// Additionally track, for every element of the [op stack](Self::op_stack), the
+    /// step that last modified it. See [`provenance`](Self::provenance).
+    ///
+    /// Tracking slows down execution considerably and is disabled by default.
+    #[must_use]
+    pub fn with_provenance_tracking(mut self) -> Self {
+        self.op_stack_provenance = Some(vec![None; self.op_stack.len()]);
+        self
+    }
+
+    /// The step that last modified the given op stack element, if
+    /// [tracked](Self::with_provenance_tracking). `None` if tracking is disabled, if
+    /// the element has not been modified since tracking began, or if the element does
+    /// not exist.
+    ///
+    /// Writing a value to a position in the stack that already held an identical value
+    /// does not count as a modification.
+    pub fn provenance(&self, stack_element: OpStackElement) -> Option<Provenance> {
+        let provenance = self.op_stack_provenance.as_ref()?;
+        let index = self
+            .op_stack
+            .len()
+            .checked_sub(usize::from(stack_element) + 1)?;
+        provenance.get(index).copied().flatten()
+    }
+
+    fn update_op_stack_provenance(
+        &mut self,
+        stack_before_step: &[BFieldElement],
+        modification: Provenance,
+    ) {
+        let Some(provenance) = self.op_stack_provenance.as_mut() else {
+            return;
+        };
+
+        provenance.resize(self.op_stack.len(), None);
+        for (index, element) in self.op_stack.stack.iter().enumerate() {
+            if stack_before_step.get(index) != Some(element) {
+                provenance[index] = Some(modification);
+            }
         }
     }
 
@@ -365,6 +578,15 @@ impl VMState {
             return Err(InstructionError::OpStackError(OpStackError::TooShallow));
         }
 
+        self.public_output_len_before_step = self.public_output.len();
+        let op_stack_len_before_step = self.op_stack.len();
+        let provenance_context = self.op_stack_provenance.is_some().then(|| {
+            let modification = Provenance {
+                cycle: self.cycle_count,
+                instruction_pointer: self.instruction_pointer,
+            };
+            (self.op_stack.stack.clone(), modification)
+        });
         self.start_recording_op_stack_calls();
         let mut co_processor_calls = match current_instruction {
             Instruction::Pop(n) => self.pop(n)?,
@@ -377,7 +599,7 @@ impl VMState {
             Instruction::Halt => self.halt(),
             Instruction::Nop => self.nop(),
             Instruction::Skiz => self.skiz()?,
-            Instruction::Call(address) => self.call(address),
+            Instruction::Call(address) => self.call(address)?,
             Instruction::Return => self.return_from_call()?,
             Instruction::Recurse => self.recurse()?,
             Instruction::RecurseOrReturn => self.recurse_or_return()?,
@@ -417,6 +639,19 @@ impl VMState {
         let op_stack_calls = self.stop_recording_op_stack_calls();
         co_processor_calls.extend(op_stack_calls);
 
+        // The Op Stack Table relies on the op stack changing exactly as announced by the
+        // instruction. Catching a violation here points to the offending instruction.
+        let op_stack_len_after_step = self.op_stack.len() as i32;
+        debug_assert_eq!(
+            op_stack_len_before_step as i32 + op_stack_delta,
+            op_stack_len_after_step,
+            "instruction `{current_instruction}` changed the op stack's size unexpectedly",
+        );
+
+        if let Some((stack_before_step, modification)) = provenance_context {
+            self.update_op_stack_provenance(&stack_before_step, modification);
+        }
+
         self.cycle_count += 1;
 
         Ok(co_processor_calls)
@@ -528,14 +763,22 @@ impl VMState {
         Ok(vec![])
     }
 
-    fn call(&mut self, call_destination: BFieldElement) -> Vec<CoProcessorCall> {
+    fn call(&mut self, call_destination: BFieldElement) -> InstructionResult<Vec<CoProcessorCall>> {
+        let is_instruction_argument = usize::try_from(call_destination.value())
+            .ok()
+            .and_then(|address| self.instruction_starts.get(address))
+            .is_some_and(|&is_instruction_start| !is_instruction_start);
+        if is_instruction_argument {
+            return Err(InstructionError::MisalignedJump(call_destination));
+        }
+
         let size_of_instruction_call = 2;
         let call_origin = (self.instruction_pointer as u32 + size_of_instruction_call).into();
         let jump_stack_entry = (call_origin, call_destination);
         self.jump_stack.push(jump_stack_entry);
 
         self.instruction_pointer = call_destination.value().try_into().unwrap();
-        vec![]
+        Ok(vec![])
     }
 
     fn return_from_call(&mut self) -> InstructionResult<Vec<CoProcessorCall>> {
@@ -603,6 +846,14 @@ impl VMState {
     }
 
     fn write_mem(&mut self, n: NumberOfWords) -> InstructionResult<Vec<CoProcessorCall>> {
+        let mut address = self.op_stack[0];
+        for _ in 0..n.num_words() {
+            if self.is_read_only(address) {
+                return Err(InstructionError::WriteToReadOnlyMemory(address));
+            }
+            address.increment();
+        }
+
         self.start_recording_ram_calls();
         let mut ram_pointer = self.op_stack.pop()?;
         for _ in 0..n.num_words() {
@@ -635,6 +886,12 @@ impl VMState {
         ram_value
     }
 
+    fn is_read_only(&self, address: BFieldElement) -> bool {
+        self.read_only_ram_regions
+            .iter()
+            .any(|region| region.contains(&address.value()))
+    }
+
     fn ram_write(&mut self, ram_pointer: BFieldElement, ram_value: BFieldElement) {
         let ram_table_call = RamTableCall {
             clk: self.cycle_count,
@@ -991,6 +1248,7 @@ impl VMState {
             let read_element = self.public_input.pop_front().unwrap();
             self.op_stack.push(read_element);
         }
+        self.num_public_input_elements_read += n.num_words();
 
         self.instruction_pointer += 2;
         Ok(vec![])
@@ -1229,6 +1487,32 @@ impl VMState {
         Ok(digest.values())
     }
 
+    /// The part of the [public output](Self::public_output) that was written during the
+    /// most recent [step](Self::step). Allows consuming the output as it is being produced,
+    /// for example, by streaming it to some other process.
+    ///
+    /// Empty if no step has been taken yet or if the most recent step did not write
+    /// any output.
+    pub fn public_output_delta(&self) -> &[BFieldElement] {
+        self.public_output
+            .get(self.public_output_len_before_step..)
+            .unwrap_or_default()
+    }
+
+    /// Perform the state transition like [`step`](Self::step), additionally counting
+    /// the executed instruction in the given [`InstructionProfile`]. Only successful
+    /// steps are counted.
+    pub fn step_profiled(
+        &mut self,
+        profile: &mut InstructionProfile,
+    ) -> InstructionResult<Vec<CoProcessorCall>> {
+        let current_instruction = self.current_instruction()?;
+        let co_processor_calls = self.step()?;
+        profile.record(current_instruction);
+
+        Ok(co_processor_calls)
+    }
+
     /// Run Triton VM on this state to completion, or until an error occurs.
     pub fn run(&mut self) -> InstructionResult<()> {
         while !self.halting {
@@ -1237,6 +1521,221 @@ impl VMState {
         Ok(())
     }
 
+    /// Run Triton VM on this state to completion, or until an error occurs, supplying
+    /// missing input on demand. Whenever the VM runs out of public input, secret
+    /// input, or secret digests, the missing element is requested from
+    /// `supply_input`, appended to the corresponding input, and the failed step is
+    /// retried. A missing digest is requested one element at a time.
+    ///
+    /// This is useful for exploring a program's behavior without knowing all of its
+    /// input up front. A dry run is not directly provable: proving requires a
+    /// [`Claim`](crate::proof::Claim) and [`NonDeterminism`] that include the input
+    /// supplied during the dry run.
+    pub fn dry_run<F>(&mut self, mut supply_input: F) -> InstructionResult<()>
+    where
+        F: FnMut(MissingInput) -> BFieldElement,
+    {
+        while !self.halting {
+            match self.step() {
+                Ok(_) => (),
+                Err(InstructionError::EmptyPublicInput(_)) => {
+                    let element = supply_input(MissingInput::PublicInput);
+                    self.public_input.push_back(element);
+                }
+                Err(InstructionError::EmptySecretInput(_)) => {
+                    let element = supply_input(MissingInput::SecretInput);
+                    self.secret_individual_tokens.push_back(element);
+                }
+                Err(InstructionError::EmptySecretDigestInput) => {
+                    let digest =
+                        Digest::new(array::from_fn(|_| supply_input(MissingInput::SecretDigest)));
+                    self.secret_digests.push_back(digest);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Step until the [op stack](Self::op_stack) has the given depth, the VM halts, or
+    /// `max_steps` steps have been taken, whichever comes first. At least one step is
+    /// taken, unless the VM is already halting or `max_steps` is 0. This makes it
+    /// possible to run through a stack-neutral block of code, starting at that block's
+    /// stack depth.
+    pub fn step_until_stack_depth(
+        &mut self,
+        target_depth: usize,
+        max_steps: usize,
+    ) -> InstructionResult<StackDepthOutcome> {
+        let reached_target_depth = |state: &Self| state.op_stack.len() == target_depth;
+        let outcome = match self.run_until(reached_target_depth, max_steps)? {
+            RunOutcome::Halted => StackDepthOutcome::Halted,
+            RunOutcome::PredicateMet => StackDepthOutcome::ReachedTarget,
+            RunOutcome::CycleBudgetExhausted => StackDepthOutcome::ExhaustedBudget,
+        };
+        Ok(outcome)
+    }
+
+    /// Step until the given predicate holds, the VM halts, or `max_cycles` steps have
+    /// been taken, whichever comes first. The predicate is checked after every step.
+    /// At least one step is taken, unless the VM is already halting or `max_cycles`
+    /// is 0.
+    ///
+    /// If the budget is exhausted, `self` is the state after the last step, ready to
+    /// be inspected or run further.
+    pub fn run_until<P>(&mut self, predicate: P, max_cycles: usize) -> InstructionResult<RunOutcome>
+    where
+        P: Fn(&VMState) -> bool,
+    {
+        for _ in 0..max_cycles {
+            if self.halting {
+                return Ok(RunOutcome::Halted);
+            }
+            self.step()?;
+            if predicate(self) {
+                return Ok(RunOutcome::PredicateMet);
+            }
+        }
+
+        if self.halting {
+            return Ok(RunOutcome::Halted);
+        }
+        Ok(RunOutcome::CycleBudgetExhausted)
+    }
+
+    /// A program prologue that recreates this state's [op stack](Self::op_stack) and
+    /// non-zero [RAM](Self::ram), followed by a `call` to the given label and a `halt`.
+    /// Appending the code that defines the label turns a snapshot of a running VM into a
+    /// standalone program, for example, to reproduce some behavior in a test.
+    ///
+    /// Only the op stack and RAM are recreated. Notably, public input and output,
+    /// non-determinism, the jump stack, and the [Sponge](Self::sponge) state are not.
+    pub fn initializing_prologue(&self, label: &str) -> Vec<LabelledInstruction> {
+        let instr = LabelledInstruction::Instruction;
+        let mut prologue = vec![];
+
+        let non_zero_ram = self
+            .ram
+            .iter()
+            .filter(|(_, value)| !value.is_zero())
+            .sorted_by_key(|(address, _)| address.value());
+        for (&address, &value) in non_zero_ram {
+            prologue.extend([
+                instr(AnInstruction::Push(value)),
+                instr(AnInstruction::Push(address)),
+                instr(AnInstruction::WriteMem(NumberOfWords::N1)),
+                instr(AnInstruction::Pop(NumberOfWords::N1)),
+            ]);
+        }
+
+        // The op stack can never be shallower than the number of op stack registers. Hence,
+        // its bottom-most elements are overwritten in place: a new value is moved into st0
+        // without changing the stack's depth, then swapped into its final position.
+        let (bottom, top) = self.op_stack.stack.split_at(OpStackElement::COUNT);
+        for (&value, stack_element) in bottom.iter().zip(OpStackElement::iter().rev()) {
+            prologue.extend([
+                instr(AnInstruction::Push(value)),
+                instr(AnInstruction::Swap(OpStackElement::ST1)),
+                instr(AnInstruction::Pop(NumberOfWords::N1)),
+            ]);
+            if stack_element != OpStackElement::ST0 {
+                prologue.push(instr(AnInstruction::Swap(stack_element)));
+            }
+        }
+        let pushes = top.iter().map(|&value| instr(AnInstruction::Push(value)));
+        prologue.extend(pushes);
+
+        prologue.push(instr(AnInstruction::Call(label.to_string())));
+        prologue.push(instr(AnInstruction::Halt));
+        prologue
+    }
+
+    /// The difference between `self` and `other`, such that
+    /// [applying](Self::apply_delta) it to `self` results in `other`. Applying
+    /// `other.diff(self)` to `other` reverts it back to `self`.
+    ///
+    /// Both states must be running the same program, which is not part of the
+    /// delta. The delta is small if the states are close to each other, for
+    /// example, if they are only a few steps apart: only changed RAM entries,
+    /// changed parts of the stacks, and otherwise changed fields are recorded.
+    ///
+    /// Bookkeeping that only lives for the duration of a single [step](Self::step)
+    /// is not part of the delta.
+    pub fn diff(&self, other: &VMState) -> StateDelta {
+        let mut ram = other
+            .ram
+            .iter()
+            .filter(|&(address, value)| self.ram.get(address) != Some(value))
+            .map(|(&address, &value)| (address, Some(value)))
+            .collect::<HashMap<_, _>>();
+        let removed_ram = self.ram.keys().filter(|a| !other.ram.contains_key(a));
+        ram.extend(removed_ram.map(|&address| (address, None)));
+
+        StateDelta {
+            ram,
+            op_stack: SuffixDelta::new(&self.op_stack.stack, &other.op_stack.stack),
+            jump_stack: SuffixDelta::new(&self.jump_stack, &other.jump_stack),
+            public_output: SuffixDelta::new(&self.public_output, &other.public_output),
+            public_input: FieldDelta::new(&self.public_input, &other.public_input),
+            secret_individual_tokens: FieldDelta::new(
+                &self.secret_individual_tokens,
+                &other.secret_individual_tokens,
+            ),
+            secret_digests: FieldDelta::new(&self.secret_digests, &other.secret_digests),
+            read_only_ram_regions: FieldDelta::new(
+                &self.read_only_ram_regions,
+                &other.read_only_ram_regions,
+            ),
+            sponge: FieldDelta::new(&self.sponge, &other.sponge),
+            op_stack_provenance: FieldDelta::new(
+                &self.op_stack_provenance,
+                &other.op_stack_provenance,
+            ),
+            num_public_input_elements_read: other.num_public_input_elements_read,
+            public_output_len_before_step: other.public_output_len_before_step,
+            cycle_count: other.cycle_count,
+            instruction_pointer: other.instruction_pointer,
+            halting: other.halting,
+        }
+    }
+
+    /// Apply a [delta](Self::diff), turning `self` into the state the delta was
+    /// computed against.
+    ///
+    /// Deltas are meant to be applied to the state they were computed from.
+    /// Applying a delta to any other state results in an unspecified, though
+    /// memory-safe, state.
+    pub fn apply_delta(&mut self, delta: &StateDelta) {
+        for (&address, &value) in &delta.ram {
+            match value {
+                Some(value) => self.ram.insert(address, value),
+                None => self.ram.remove(&address),
+            };
+        }
+
+        delta.op_stack.apply(&mut self.op_stack.stack);
+        delta.jump_stack.apply(&mut self.jump_stack);
+        delta.public_output.apply(&mut self.public_output);
+        delta.public_input.apply(&mut self.public_input);
+        delta
+            .secret_individual_tokens
+            .apply(&mut self.secret_individual_tokens);
+        delta.secret_digests.apply(&mut self.secret_digests);
+        delta
+            .read_only_ram_regions
+            .apply(&mut self.read_only_ram_regions);
+        delta.sponge.apply(&mut self.sponge);
+        delta
+            .op_stack_provenance
+            .apply(&mut self.op_stack_provenance);
+
+        self.num_public_input_elements_read = delta.num_public_input_elements_read;
+        self.public_output_len_before_step = delta.public_output_len_before_step;
+        self.cycle_count = delta.cycle_count;
+        self.instruction_pointer = delta.instruction_pointer;
+        self.halting = delta.halting;
+    }
+
     fn contextualized_assertion_error(
         &self,
         expected: BFieldElement,
@@ -1254,6 +1753,188 @@ impl VMState {
     }
 }
 
+/// The difference between two [`VMState`]s. See [`VMState::diff`].
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StateDelta {
+    /// Changed and added RAM entries with their new value, and removed RAM
+    /// entries with `None`.
+    ram: HashMap<BFieldElement, Option<BFieldElement>>,
+    op_stack: SuffixDelta<BFieldElement>,
+    jump_stack: SuffixDelta<(BFieldElement, BFieldElement)>,
+    public_output: SuffixDelta<BFieldElement>,
+
+    // Fields that rarely change are only recorded if they did.
+    public_input: FieldDelta<VecDeque<BFieldElement>>,
+    secret_individual_tokens: FieldDelta<VecDeque<BFieldElement>>,
+    secret_digests: FieldDelta<VecDeque<Digest>>,
+    read_only_ram_regions: FieldDelta<Vec<Range<u64>>>,
+    sponge: FieldDelta<Option<Tip5>>,
+    op_stack_provenance: FieldDelta<Option<Vec<Option<Provenance>>>>,
+
+    num_public_input_elements_read: usize,
+    public_output_len_before_step: usize,
+    cycle_count: u32,
+    instruction_pointer: usize,
+    halting: bool,
+}
+
+/// The difference between two stack-like sequences: the length of their common
+/// prefix, and the remainder of the new sequence.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+struct SuffixDelta<T> {
+    common_prefix_len: usize,
+    new_suffix: Vec<T>,
+}
+
+impl<T: Clone + PartialEq> SuffixDelta<T> {
+    fn new(old: &[T], new: &[T]) -> Self {
+        let common_prefix_len = old.iter().zip(new).take_while(|(o, n)| o == n).count();
+        let new_suffix = new[common_prefix_len..].to_vec();
+
+        Self {
+            common_prefix_len,
+            new_suffix,
+        }
+    }
+
+    fn apply(&self, sequence: &mut Vec<T>) {
+        sequence.truncate(self.common_prefix_len);
+        sequence.extend_from_slice(&self.new_suffix);
+    }
+}
+
+/// The new value of some field, if it changed.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+enum FieldDelta<T> {
+    Unchanged,
+    Changed(T),
+}
+
+impl<T: Clone + PartialEq> FieldDelta<T> {
+    fn new(old: &T, new: &T) -> Self {
+        if old == new {
+            Self::Unchanged
+        } else {
+            Self::Changed(new.clone())
+        }
+    }
+
+    fn apply(&self, field: &mut T) {
+        if let Self::Changed(new) = self {
+            field.clone_from(new);
+        }
+    }
+}
+
+/// The step that last modified some element of the op stack. See
+/// [`VMState::provenance`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize, Arbitrary)]
+pub struct Provenance {
+    /// The [cycle count](VMState::cycle_count) at the beginning of the modifying step.
+    pub cycle: u32,
+
+    /// The address of the modifying instruction.
+    pub instruction_pointer: usize,
+}
+
+/// The kind of input requested during a [dry run](VMState::dry_run).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Arbitrary)]
+pub enum MissingInput {
+    /// An element of [public input](VMState::public_input).
+    PublicInput,
+
+    /// An element of [secret input](VMState::secret_individual_tokens).
+    SecretInput,
+
+    /// One element of a [secret digest](VMState::secret_digests).
+    SecretDigest,
+}
+
+/// The reason [`VMState::run_until`] stopped stepping.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Arbitrary)]
+pub enum RunOutcome {
+    /// The VM halted before the predicate was met.
+    Halted,
+
+    /// The predicate holds for the state after the most recent step.
+    PredicateMet,
+
+    /// The maximum number of steps was taken without the predicate being met.
+    CycleBudgetExhausted,
+}
+
+/// The reason [`VMState::step_until_stack_depth`] stopped stepping.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Arbitrary)]
+pub enum StackDepthOutcome {
+    /// The op stack has the requested depth.
+    ReachedTarget,
+
+    /// The VM halted before the op stack reached the requested depth.
+    Halted,
+
+    /// The maximum number of steps was taken without the op stack reaching the
+    /// requested depth.
+    ExhaustedBudget,
+}
+
+/// How often each instruction was executed, collected through
+/// [`VMState::step_profiled`]. Instructions are counted per variant, _i.e._,
+/// regardless of their argument: `push 1` and `push 2` both count towards `push`.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct InstructionProfile {
+    counts: HashMap<u32, u64>,
+}
+
+/// One line of an [`InstructionProfile`]'s [report](InstructionProfile::report).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct InstructionProfileEntry {
+    /// The profiled instruction. Since instructions are counted per variant, the
+    /// argument, if any, carries no meaning.
+    pub instruction: Instruction,
+
+    /// The number of times the instruction was executed.
+    pub count: u64,
+
+    /// The share of this instruction in all executed instructions, between 0 and 1.
+    pub fraction: f64,
+}
+
+impl InstructionProfile {
+    /// Count one execution of the given instruction.
+    pub fn record(&mut self, instruction: Instruction) {
+        *self.counts.entry(instruction.opcode()).or_default() += 1;
+    }
+
+    /// The total number of executed instructions.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Forget all counts, for example, when restarting the program.
+    pub fn reset(&mut self) {
+        self.counts.clear();
+    }
+
+    /// All executed instructions, the most frequently executed first. Ties are
+    /// broken by opcode.
+    pub fn report(&self) -> Vec<InstructionProfileEntry> {
+        let total = self.total() as f64;
+        ALL_INSTRUCTIONS
+            .into_iter()
+            .filter_map(|instruction| {
+                let count = *self.counts.get(&instruction.opcode())?;
+                let fraction = count as f64 / total;
+                Some(InstructionProfileEntry {
+                    instruction,
+                    count,
+                    fraction,
+                })
+            })
+            .sorted_by_key(|entry| (Reverse(entry.count), entry.instruction.opcode()))
+            .collect()
+    }
+}
+
 impl Display for VMState {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         use ProcessorMainColumn as ProcCol;
@@ -1380,7 +2061,7 @@ impl Display for VMState {
             let max_label_width = total_width - idx_width - 2; // for `: `
 
             for (idx, &(_, address)) in self.jump_stack.iter().rev().enumerate() {
-                let label = self.program.label_for_address(address.value());
+                let label = self.program.nearest_label_for_address(address.value());
                 let label = label.chars().take(max_label_width).collect::<String>();
                 print_row(f, format!("{idx:>idx_width$}: {label}"))?;
                 print_row(f, format!("        at {address}"))?;
@@ -1443,6 +2124,57 @@ impl PublicInput {
     pub fn new(individual_tokens: Vec<BFieldElement>) -> Self {
         Self { individual_tokens }
     }
+
+    /// Parse public input from a string of whitespace- or comma-separated integers.
+    /// Depending on the [`ParseMode`], integers that are not canonical representatives
+    /// of [`BFieldElement`]s are either rejected or reduced modulo
+    /// [`BFieldElement::P`].
+    ///
+    /// See also the [`FromStr`] implementation, which uses [`ParseMode::Strict`].
+    pub fn parse_with_mode(s: &str, mode: ParseMode) -> Result<Self, ParseBFieldElementError> {
+        let individual_tokens = s
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .map(|token| mode.parse_bfe(token))
+            .try_collect()?;
+
+        Ok(Self::new(individual_tokens))
+    }
+}
+
+impl FromStr for PublicInput {
+    type Err = ParseBFieldElementError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with_mode(s, ParseMode::Strict)
+    }
+}
+
+/// How to treat integers that are not canonical representatives of
+/// [`BFieldElement`]s, _i.e._, integers greater than or equal to
+/// [`BFieldElement::P`], when parsing them.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Arbitrary)]
+pub enum ParseMode {
+    /// Reject non-canonical integers.
+    #[default]
+    Strict,
+
+    /// Reduce non-canonical integers modulo [`BFieldElement::P`]. Helpful when
+    /// pasting values from other systems. Integers that do not fit in a `u128` are
+    /// still rejected.
+    ReduceModP,
+}
+
+impl ParseMode {
+    fn parse_bfe(self, s: &str) -> Result<BFieldElement, ParseBFieldElementError> {
+        match self {
+            Self::Strict => s.parse(),
+            Self::ReduceModP => s
+                .parse::<u128>()
+                .map(BFieldElement::from)
+                .map_err(ParseBFieldElementError::ParseU64Error),
+        }
+    }
 }
 
 /// All sources of non-determinism for a program. This includes elements that
@@ -1453,6 +2185,15 @@ pub struct NonDeterminism {
     pub individual_tokens: Vec<BFieldElement>,
     pub digests: Vec<Digest>,
     pub ram: HashMap<BFieldElement, BFieldElement>,
+
+    /// Address ranges of [RAM](Self::ram) that are read-only. Instruction `write_mem`
+    /// fails with [`InstructionError::WriteToReadOnlyMemory`] if it targets any
+    /// address in these ranges. Useful to protect, for example, large tables that
+    /// are only supposed to be read.
+    ///
+    /// This is a debugging aid only: it is not enforced by the STARK.
+    #[serde(default)]
+    pub read_only_ram_regions: Vec<Range<u64>>,
 }
 
 impl From<Vec<BFieldElement>> for NonDeterminism {
@@ -1485,9 +2226,16 @@ impl NonDeterminism {
             individual_tokens: individual_tokens.into(),
             digests: vec![],
             ram: HashMap::new(),
+            read_only_ram_regions: vec![],
         }
     }
 
+    #[must_use]
+    pub fn with_individual_tokens<V: Into<Vec<BFieldElement>>>(mut self, tokens: V) -> Self {
+        self.individual_tokens = tokens.into();
+        self
+    }
+
     #[must_use]
     pub fn with_digests<V: Into<Vec<Digest>>>(mut self, digests: V) -> Self {
         self.digests = digests.into();
@@ -1499,6 +2247,237 @@ impl NonDeterminism {
         self.ram = ram.into();
         self
     }
+
+    /// Append the given tokens to the already present
+    /// [individual tokens](Self::individual_tokens).
+    #[must_use]
+    pub fn extend_individual_tokens<I>(mut self, tokens: I) -> Self
+    where
+        I: IntoIterator<Item = BFieldElement>,
+    {
+        self.individual_tokens.extend(tokens);
+        self
+    }
+
+    /// Append the given digests to the already present [digests](Self::digests).
+    #[must_use]
+    pub fn extend_digests<I: IntoIterator<Item = Digest>>(mut self, digests: I) -> Self {
+        self.digests.extend(digests);
+        self
+    }
+
+    /// Add the given entries to the already present [RAM](Self::ram). An entry for
+    /// an address that is already present overwrites the previous entry.
+    #[must_use]
+    pub fn extend_ram<I>(mut self, ram: I) -> Self
+    where
+        I: IntoIterator<Item = (BFieldElement, BFieldElement)>,
+    {
+        self.ram.extend(ram);
+        self
+    }
+
+    /// Mark the given address ranges of [RAM](Self::ram) as read-only, replacing any
+    /// previously present [read-only regions](Self::read_only_ram_regions).
+    #[must_use]
+    pub fn with_read_only_ram_regions<V: Into<Vec<Range<u64>>>>(mut self, regions: V) -> Self {
+        self.read_only_ram_regions = regions.into();
+        self
+    }
+
+    /// Pseudo-randomly generate non-determinism with the given number of
+    /// [individual tokens](Self::individual_tokens) and [digests](Self::digests).
+    /// The [RAM](Self::ram) is left empty.
+    ///
+    /// The same seed always results in the same non-determinism, which makes this
+    /// useful for fuzzing and regression tests. It is not a substitute for actual
+    /// secret input.
+    pub fn from_seed(seed: [u8; 32], num_tokens: usize, num_digests: usize) -> Self {
+        // Unlike `StdRng`, `ChaCha12Rng` is guaranteed to be reproducible across
+        // versions of `rand`.
+        let mut rng = ChaCha12Rng::from_seed(seed);
+        let individual_tokens = (0..num_tokens).map(|_| rng.gen()).collect();
+        let digests = (0..num_digests).map(|_| rng.gen()).collect();
+
+        Self {
+            individual_tokens,
+            digests,
+            ram: HashMap::new(),
+            read_only_ram_regions: vec![],
+        }
+    }
+}
+
+/// A memory-efficient record of a completed execution of Triton VM.
+///
+/// Since execution is deterministic, the program together with the inputs it actually
+/// consumed fully determines every intermediate [`VMState`]. A `CompactTrace` stores only
+/// those, plus the outcome of every branching instruction, and reconstructs the state
+/// at any cycle [by replaying](Self::replay_until) the execution from the start. This
+/// trades computation for memory when compared to storing every intermediate state.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CompactTrace {
+    program: Program,
+    public_input: Vec<BFieldElement>,
+    non_determinism: NonDeterminism,
+    branch_decisions: Vec<bool>,
+    num_cycles: u32,
+}
+
+impl CompactTrace {
+    /// Run the given program to completion, recording a compact trace of the execution.
+    pub fn record(
+        program: Program,
+        public_input: PublicInput,
+        non_determinism: NonDeterminism,
+    ) -> VMResult<Self> {
+        let mut state = VMState::new(program.clone(), public_input.clone(), non_determinism);
+        let initial_ram = state.ram.clone();
+        let secret_individual_tokens = state.secret_individual_tokens.clone();
+        let secret_digests = state.secret_digests.clone();
+
+        let mut branch_decisions = vec![];
+        while !state.halting {
+            match Self::step(&mut state) {
+                Ok(branch_decision) => branch_decisions.extend(branch_decision),
+                Err(err) => return Err(VMError::new(err, state)),
+            }
+        }
+
+        let num_read_public = public_input.len() - state.public_input.len();
+        let num_read_secret = secret_individual_tokens.len() - state.secret_individual_tokens.len();
+        let num_read_digests = secret_digests.len() - state.secret_digests.len();
+        let non_determinism = NonDeterminism {
+            individual_tokens: secret_individual_tokens
+                .into_iter()
+                .take(num_read_secret)
+                .collect(),
+            digests: secret_digests.into_iter().take(num_read_digests).collect(),
+            ram: initial_ram,
+            read_only_ram_regions: state.read_only_ram_regions,
+        };
+
+        Ok(Self {
+            program,
+            public_input: public_input[..num_read_public].to_vec(),
+            non_determinism,
+            branch_decisions,
+            num_cycles: state.cycle_count,
+        })
+    }
+
+    /// Perform one step, returning the outcome if the executed instruction is branching.
+    /// For `skiz`, the outcome is whether the next instruction is executed. For
+    /// `recurse_or_return`, the outcome is whether execution recurses.
+    fn step(state: &mut VMState) -> InstructionResult<Option<bool>> {
+        let instruction = state.current_instruction()?;
+        let instruction_pointer = state.instruction_pointer;
+        let jump_stack_len = state.jump_stack.len();
+        state.step()?;
+
+        let decision = match instruction {
+            Instruction::Skiz => Some(state.instruction_pointer == instruction_pointer + 1),
+            Instruction::RecurseOrReturn => Some(state.jump_stack.len() == jump_stack_len),
+            _ => None,
+        };
+        Ok(decision)
+    }
+
+    /// The number of cycles the recorded execution took.
+    pub fn num_cycles(&self) -> u32 {
+        self.num_cycles
+    }
+
+    /// The outcome of every executed branching instruction, in order of execution.
+    /// For `skiz`, the outcome is whether the next instruction was executed. For
+    /// `recurse_or_return`, the outcome is whether execution recursed.
+    pub fn branch_decisions(&self) -> &[bool] {
+        &self.branch_decisions
+    }
+
+    /// Reconstruct the [`VMState`] right before the given cycle is executed.
+    ///
+    /// Returns `None` if the cycle exceeds the recorded execution, or if the replayed
+    /// execution diverges from the recorded branch decisions.
+    pub fn replay_until(&self, cycle: u32) -> Option<VMState> {
+        if cycle > self.num_cycles {
+            return None;
+        }
+
+        let public_input = PublicInput::new(self.public_input.clone());
+        let non_determinism = self.non_determinism.clone();
+        let mut state = VMState::new(self.program.clone(), public_input, non_determinism);
+        let mut recorded_decisions = self.branch_decisions.iter();
+        while state.cycle_count < cycle {
+            let decision = Self::step(&mut state).ok()?;
+            if decision.is_some() && decision.as_ref() != recorded_decisions.next() {
+                return None;
+            }
+        }
+
+        Some(state)
+    }
+}
+
+/// A snapshot of the most relevant parts of a [`VMState`] right before some clock
+/// cycle. See [`VM::trace_to_json`].
+///
+/// When serialized, [`BFieldElement`]s are represented as strings holding their
+/// canonical, decimal value. This avoids precision loss in consumers that represent
+/// all numbers as floating point values, like JavaScript.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TraceRecord {
+    pub cycle: u32,
+    pub instruction_pointer: usize,
+
+    /// The instruction about to be executed, in its textual representation.
+    pub instruction: String,
+
+    /// The entire op stack. Unlike [`OpStack::stack`], the top of the stack comes first.
+    #[serde(with = "decimal_strings")]
+    pub op_stack: Vec<BFieldElement>,
+
+    pub jump_stack_depth: usize,
+}
+
+impl TryFrom<&VMState> for TraceRecord {
+    type Error = InstructionError;
+
+    fn try_from(state: &VMState) -> InstructionResult<Self> {
+        let record = Self {
+            cycle: state.cycle_count,
+            instruction_pointer: state.instruction_pointer,
+            instruction: state.current_instruction()?.to_string(),
+            op_stack: state.op_stack.top_n(state.op_stack.len()).collect(),
+            jump_stack_depth: state.jump_stack.len(),
+        };
+        Ok(record)
+    }
+}
+
+/// (De)serialize [`BFieldElement`]s as strings of their canonical, decimal value.
+mod decimal_strings {
+    use serde::de::Error;
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serializer;
+    use twenty_first::prelude::BFieldElement;
+
+    pub fn serialize<S: Serializer>(
+        elements: &[BFieldElement],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(elements.iter().map(|element| element.value().to_string()))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<BFieldElement>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|element| element.parse().map_err(D::Error::custom))
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -1584,6 +2563,80 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn json_trace_has_one_record_per_clock_cycle() {
+        let program = crate::example_programs::FIBONACCI_SEQUENCE.clone();
+        let public_input = PublicInput::from(bfe_array![7]);
+        let_assert!(Ok(json) = VM::trace_to_json(program.clone(), public_input.clone(), [].into()));
+        let_assert!(Ok(records) = serde_json::from_str::<Vec<TraceRecord>>(&json));
+        let_assert!(Ok(cycle_count) = VM::clock_cycle_count(program, public_input, [].into()));
+        assert!(records.len() == usize::try_from(cycle_count).unwrap());
+
+        for (cycle, record) in (0..).zip(&records) {
+            assert!(cycle == record.cycle);
+        }
+        let_assert!(Some(last_record) = records.last());
+        assert!("halt" == last_record.instruction);
+    }
+
+    #[test]
+    fn json_trace_represents_field_elements_as_decimal_strings() {
+        let program = triton_program!(push -1 push 5 halt);
+        let_assert!(Ok(json) = VM::trace_to_json(program, [].into(), [].into()));
+        let_assert!(Ok(records) = serde_json::from_str::<serde_json::Value>(&json));
+
+        let top_of_stack_at_halt = &records[2]["op_stack"][0];
+        assert!(Some("5") == top_of_stack_at_halt.as_str());
+        let max_element = BFieldElement::MAX.to_string();
+        assert!(Some(max_element.as_str()) == records[2]["op_stack"][1].as_str());
+        assert!(Some("push 5") == records[1]["instruction"].as_str());
+    }
+
+    #[test]
+    fn json_trace_fails_with_same_error_as_regular_vm_run() {
+        let program = triton_program!(push 0 assert halt);
+        let_assert!(Err(json_err) = VM::trace_to_json(program.clone(), [].into(), [].into()));
+        let_assert!(Err(run_err) = VM::run(program, [].into(), [].into()));
+        assert!(run_err == json_err);
+    }
+
+    #[test]
+    fn cloned_vm_state_shares_program_but_not_execution_state() {
+        let program = triton_program!(push 1 push 2 add write_io 1 halt);
+        let mut state = VMState::new(program, [].into(), [].into());
+        let snapshot = state.clone();
+        assert!(Arc::ptr_eq(&state.program, &snapshot.program));
+
+        let_assert!(Ok(()) = state.run());
+        assert!(0 == snapshot.cycle_count);
+        assert!(0 == snapshot.instruction_pointer);
+        assert!(snapshot.public_output.is_empty());
+        assert!(bfe_vec![3] == state.public_output);
+    }
+
+    #[test]
+    fn clock_cycle_count_equals_processor_table_height() {
+        let program = crate::example_programs::FIBONACCI_SEQUENCE.clone();
+        let public_input = PublicInput::from(bfe_array![20]);
+        let_assert!(
+            Ok(cycle_count) =
+                VM::clock_cycle_count(program.clone(), public_input.clone(), [].into())
+        );
+        let_assert!(Ok((aet, _)) = VM::trace_execution(program, public_input, [].into()));
+        let processor_height = u32::try_from(aet.height_of_table(TableId::Processor)).unwrap();
+        assert!(processor_height == cycle_count);
+    }
+
+    #[test]
+    fn clock_cycle_count_fails_with_same_error_as_regular_vm_run() {
+        let program = triton_program!(push 0 assert halt);
+        let_assert!(
+            Err(cycle_count_err) = VM::clock_cycle_count(program.clone(), [].into(), [].into())
+        );
+        let_assert!(Err(run_err) = VM::run(program, [].into(), [].into()));
+        assert!(run_err == cycle_count_err);
+    }
+
     #[test]
     fn profile_can_be_created_and_agrees_with_regular_vm_run() {
         let program =
@@ -1614,6 +2667,69 @@ pub(crate) mod tests {
         println!("{profile}");
     }
 
+    #[test]
+    fn weighted_cost_of_profile_equals_hand_computed_value() {
+        let program = triton_program! {
+            push 2 push 3 add   // 2 + 2 + 5
+            push 4 mul          // 2 + 7
+            call foo            // 1
+            write_io 1          // 1
+            halt                // 1
+            foo: return         // 1
+        };
+        let cost_model = CostModel::default()
+            .with_weight(Instruction::Push(bfe!(0)), 2)
+            .with_weight(Instruction::Add, 5)
+            .with_weight(Instruction::Mul, 7);
+
+        let_assert!(
+            Ok((_, profile)) =
+                VM::profile_with_cost_model(program.clone(), [].into(), [].into(), cost_model)
+        );
+        assert!(22 == profile.weighted_cost);
+
+        let_assert!(Ok((_, profile)) = VM::profile(program, [].into(), [].into()));
+        assert!(u64::from(profile.total.processor) == profile.weighted_cost);
+    }
+
+    #[test]
+    fn wall_time_is_only_recorded_when_requested() {
+        let program = triton_program! {
+            push 2 push 3 add
+            sponge_init
+            call foo
+            halt
+            foo: return
+        };
+
+        let_assert!(Ok((_, profile)) = VM::profile(program.clone(), [].into(), [].into()));
+        assert!(profile.wall_time.is_empty());
+
+        let cost_model = CostModel::default();
+        let_assert!(
+            Ok((_, profile)) =
+                VM::profile_with_wall_time(program, [].into(), [].into(), cost_model)
+        );
+        let recorded = profile.wall_time.keys().map(String::as_str).collect_vec();
+        assert!(["add", "call", "halt", "push", "return", "sponge_init"] == *recorded);
+        assert!(profile.to_string().contains("Wall Time"));
+    }
+
+    #[test]
+    fn calling_the_argument_of_an_instruction_crashes_vm() {
+        let mut program = triton_program! {
+            push 42 call foo halt
+            foo: return
+        };
+        let misaligned_call = Instruction::Call(bfe!(1));
+        program.instructions[2] = misaligned_call;
+        program.instructions[3] = misaligned_call;
+
+        let_assert!(Err(err) = VM::run(program, [].into(), [].into()));
+        let_assert!(InstructionError::MisalignedJump(target) = err.source);
+        assert!(bfe!(1) == target);
+    }
+
     #[test]
     fn program_with_too_many_returns_crashes_vm_but_not_profiler() {
         let program = triton_program! {
@@ -1648,6 +2764,229 @@ pub(crate) mod tests {
         assert!(NonDeterminism::new(vec![]) == [].into());
     }
 
+    #[test]
+    fn extending_non_determinism_appends_in_composition_order() {
+        let digests = [Digest::new(bfe_array![1, 2, 3, 4, 5]), Digest::default()];
+        let non_determinism = NonDeterminism::new(bfe_vec![1, 2])
+            .extend_individual_tokens(bfe_vec![3])
+            .extend_individual_tokens(bfe_vec![4, 5])
+            .with_digests(digests[..1].to_vec())
+            .extend_digests(digests[1..].to_vec())
+            .with_ram([(bfe!(0), bfe!(10)), (bfe!(1), bfe!(11))])
+            .extend_ram([(bfe!(1), bfe!(21)), (bfe!(2), bfe!(22))]);
+
+        assert!(bfe_vec![1, 2, 3, 4, 5] == non_determinism.individual_tokens);
+        assert!(digests.to_vec() == non_determinism.digests);
+
+        let expected_ram = [(0, 10), (1, 21), (2, 22)].map(|(k, v)| (bfe!(k), bfe!(v)));
+        assert!(HashMap::from(expected_ram) == non_determinism.ram);
+    }
+
+    #[test]
+    fn with_individual_tokens_replaces_present_tokens() {
+        let non_determinism = NonDeterminism::new(bfe_vec![1, 2])
+            .with_individual_tokens(bfe_vec![3])
+            .extend_individual_tokens(bfe_vec![4]);
+        assert!(bfe_vec![3, 4] == non_determinism.individual_tokens);
+    }
+
+    #[proptest]
+    fn non_determinism_from_same_seed_is_identical(
+        seed: [u8; 32],
+        #[strategy(0_usize..100)] num_tokens: usize,
+        #[strategy(0_usize..20)] num_digests: usize,
+    ) {
+        let non_determinism = NonDeterminism::from_seed(seed, num_tokens, num_digests);
+        let other_non_determinism = NonDeterminism::from_seed(seed, num_tokens, num_digests);
+        prop_assert_eq!(&non_determinism, &other_non_determinism);
+
+        let serialized = serde_json::to_vec(&non_determinism).unwrap();
+        let other_serialized = serde_json::to_vec(&other_non_determinism).unwrap();
+        prop_assert_eq!(serialized, other_serialized);
+    }
+
+    #[test]
+    fn non_determinism_from_seed_has_requested_dimensions() {
+        let non_determinism = NonDeterminism::from_seed([42; 32], 17, 5);
+        assert!(17 == non_determinism.individual_tokens.len());
+        assert!(5 == non_determinism.digests.len());
+        assert!(non_determinism.ram.is_empty());
+    }
+
+    #[test]
+    fn non_determinism_from_seed_is_stable() {
+        let non_determinism = NonDeterminism::from_seed([42; 32], 3, 1);
+        let expected_tokens = bfe_vec![
+            4_374_362_904_215_662_648_u64,
+            15_875_378_420_246_977_347_u64,
+            4_919_516_990_057_815_086_u64,
+        ];
+        let expected_digest = Digest::new(bfe_array![
+            18_133_584_223_987_644_142_u64,
+            882_865_312_182_375_604_u64,
+            8_276_477_926_628_601_322_u64,
+            453_656_555_673_461_697_u64,
+            13_682_914_579_467_699_232_u64,
+        ]);
+        assert!(expected_tokens == non_determinism.individual_tokens);
+        assert!(vec![expected_digest] == non_determinism.digests);
+    }
+
+    #[test]
+    fn non_determinism_from_different_seeds_differs() {
+        let non_determinism = NonDeterminism::from_seed([0; 32], 10, 2);
+        let other_non_determinism = NonDeterminism::from_seed([1; 32], 10, 2);
+        assert!(non_determinism != other_non_determinism);
+    }
+
+    #[test]
+    fn instruction_profile_counts_executed_instructions_per_variant() {
+        let program = triton_program!(push 1 push 2 push 3 pop 3 halt);
+        let mut vm_state = VMState::new(program, PublicInput::default(), NonDeterminism::default());
+        let mut profile = InstructionProfile::default();
+        while !vm_state.halting {
+            vm_state.step_profiled(&mut profile).unwrap();
+        }
+
+        assert!(5 == profile.total());
+        assert!(vm_state.cycle_count == u32::try_from(profile.total()).unwrap());
+
+        let report = profile.report();
+        assert!(3 == report.len());
+        assert!("push" == report[0].instruction.name());
+        assert!(3 == report[0].count);
+        assert!(0.6 == report[0].fraction);
+
+        let fraction_sum = report.iter().map(|entry| entry.fraction).sum::<f64>();
+        assert!((fraction_sum - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn instruction_profile_survives_steps_but_not_reset() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let mut vm_state = VMState::new(program, PublicInput::default(), NonDeterminism::default());
+        let mut profile = InstructionProfile::default();
+        vm_state.step_profiled(&mut profile).unwrap();
+        vm_state.step_profiled(&mut profile).unwrap();
+        assert!(2 == profile.total());
+
+        profile.reset();
+        assert!(0 == profile.total());
+        assert!(profile.report().is_empty());
+    }
+
+    #[test]
+    fn failing_step_is_not_profiled() {
+        let program = triton_program!(push 0 assert halt);
+        let mut vm_state = VMState::new(program, PublicInput::default(), NonDeterminism::default());
+        let mut profile = InstructionProfile::default();
+        vm_state.step_profiled(&mut profile).unwrap();
+        let_assert!(Err(_) = vm_state.step_profiled(&mut profile));
+        assert!(1 == profile.total());
+    }
+
+    #[test]
+    fn writing_to_read_only_ram_fails() {
+        let program = triton_program!(push 42 push 10 write_mem 1 pop 1 halt);
+        let non_determinism = NonDeterminism::default()
+            .extend_ram([(bfe!(10), bfe!(7))])
+            .with_read_only_ram_regions([0..4, 10..20]);
+        let_assert!(Err(err) = VM::run(program, PublicInput::default(), non_determinism));
+        let_assert!(InstructionError::WriteToReadOnlyMemory(address) = err.source);
+        assert!(bfe!(10) == address);
+        assert!(bfe!(7) == err.vm_state.ram[&bfe!(10)]);
+    }
+
+    #[test]
+    fn partially_overlapping_write_to_read_only_ram_fails_without_writing_anything() {
+        let program = triton_program!(push 3 push 2 push 1 push 8 write_mem 3 pop 1 halt);
+        let non_determinism = NonDeterminism::default().with_read_only_ram_regions([0..4, 10..20]);
+        let_assert!(Err(err) = VM::run(program, PublicInput::default(), non_determinism));
+        let_assert!(InstructionError::WriteToReadOnlyMemory(address) = err.source);
+        assert!(bfe!(10) == address);
+        assert!(err.vm_state.ram.is_empty());
+        assert!(bfe!(8) == err.vm_state.op_stack[0]);
+    }
+
+    #[test]
+    fn read_only_ram_can_be_read_and_ram_outside_of_it_can_be_written() {
+        let program = triton_program! {
+            push 10 read_mem 1 pop 1 write_io 1
+            push 42 push 20 write_mem 1 pop 1
+            halt
+        };
+        let non_determinism = NonDeterminism::default()
+            .extend_ram([(bfe!(10), bfe!(7))])
+            .with_read_only_ram_regions([0..4, 10..20]);
+        let_assert!(Ok(output) = VM::run(program, PublicInput::default(), non_determinism));
+        assert!(bfe_vec![7] == output);
+    }
+
+    fn states_of_every_step(program: Program) -> Vec<VMState> {
+        let mut state = VMState::new(program, [].into(), [].into()).with_provenance_tracking();
+        let mut states = vec![state.clone()];
+        while !state.halting {
+            state.step().unwrap();
+            states.push(state.clone());
+        }
+        states
+    }
+
+    #[test]
+    fn applying_delta_between_consecutive_states_reconstructs_later_state() {
+        let program = triton_program! {
+            push 5 push 42 write_mem 1 pop 1
+            sponge_init push 1 push 2 push 3 push 4 push 5
+            push 6 push 7 push 8 push 9 push 10 sponge_absorb
+            call foo push 42 read_mem 1 pop 1 write_io 1 halt
+            foo: push 0 push 42 write_mem 1 pop 1 return
+        };
+        for (earlier, later) in states_of_every_step(program).into_iter().tuple_windows() {
+            let mut state = earlier.clone();
+            state.apply_delta(&earlier.diff(&later));
+            assert!(later == state);
+
+            state.apply_delta(&later.diff(&earlier));
+            assert!(earlier == state);
+        }
+    }
+
+    #[test]
+    fn applying_delta_between_distant_states_reconstructs_later_state() {
+        let program = crate::example_programs::FIBONACCI_SEQUENCE.clone();
+        let mut state = VMState::new(program, bfe_vec![20].into(), [].into());
+        let initial_state = state.clone();
+        state.run().unwrap();
+
+        let mut reconstructed = initial_state.clone();
+        reconstructed.apply_delta(&initial_state.diff(&state));
+        assert!(state == reconstructed);
+    }
+
+    #[test]
+    fn delta_of_memory_write_only_records_written_ram_entry() {
+        let program = triton_program!(push 5 push 42 write_mem 1 pop 1 halt);
+        let states = states_of_every_step(program);
+        let delta = states[2].diff(&states[3]);
+
+        assert!(HashMap::from([(bfe!(42), Some(bfe!(5)))]) == delta.ram);
+        assert!(FieldDelta::Unchanged == delta.public_input);
+        assert!(FieldDelta::Unchanged == delta.sponge);
+    }
+
+    #[test]
+    fn delta_between_identical_states_changes_nothing() {
+        let program = crate::example_programs::FIBONACCI_SEQUENCE.clone();
+        let state = VMState::new(program, bfe_vec![20].into(), [].into());
+        let delta = state.diff(&state);
+        assert!(delta.ram.is_empty());
+        assert!(delta.op_stack.new_suffix.is_empty());
+
+        let mut reconstructed = state.clone();
+        reconstructed.apply_delta(&delta);
+        assert!(state == reconstructed);
+    }
+
     #[test]
     fn initialise_table() {
         let program = crate::example_programs::GREATEST_COMMON_DIVISOR.clone();
@@ -1669,6 +3008,14 @@ pub(crate) mod tests {
         assert!(bfe!(14) == stdout[0]);
     }
 
+    #[test]
+    fn run_tvm_gcd_matches_snapshot() {
+        let program = crate::example_programs::GREATEST_COMMON_DIVISOR.clone();
+        ProgramAndInput::new(program)
+            .with_input(bfe_array![42, 56])
+            .assert_run_matches_snapshot("gcd_42_56");
+    }
+
     #[test]
     fn crash_triton_vm_and_print_vm_error() {
         let crashing_program = triton_program!(push 2 assert halt);
@@ -1707,6 +3054,60 @@ pub(crate) mod tests {
         }
     }
 
+    #[test]
+    fn concatenated_public_output_deltas_equal_final_public_output() {
+        let program = triton_program! {
+            push 1 write_io 1
+            push 2 push 3 write_io 2
+            push 4 pop 1
+            read_io 3 write_io 3
+            halt
+        };
+        let public_input = PublicInput::from(bfe_array![5, 6, 7]);
+        let mut state = VMState::new(program, public_input, [].into());
+        assert!(state.public_output_delta().is_empty());
+
+        let mut streamed_output = vec![];
+        while !state.halting {
+            state.step().unwrap();
+            streamed_output.extend_from_slice(state.public_output_delta());
+        }
+
+        assert!(bfe_vec![1, 3, 2, 7, 6, 5] == state.public_output);
+        assert!(state.public_output == streamed_output);
+    }
+
+    #[test]
+    fn states_replayed_from_compact_trace_match_reference_execution() {
+        let program = crate::example_programs::GREATEST_COMMON_DIVISOR.clone();
+        let public_input = PublicInput::from(bfe_array![42, 56]);
+        let non_determinism = NonDeterminism::default();
+        let_assert!(
+            Ok(compact_trace) = CompactTrace::record(
+                program.clone(),
+                public_input.clone(),
+                non_determinism.clone()
+            )
+        );
+
+        let mut reference_state = VMState::new(program, public_input, non_determinism);
+        let mut reference_states = vec![reference_state.clone()];
+        while !reference_state.halting {
+            reference_state.step().unwrap();
+            reference_states.push(reference_state.clone());
+        }
+
+        assert!(reference_state.cycle_count == compact_trace.num_cycles());
+        assert!(!compact_trace.branch_decisions().is_empty());
+        for (cycle, reference_state) in (0..).zip(reference_states) {
+            let_assert!(Some(replayed_state) = compact_trace.replay_until(cycle));
+            assert!(reference_state == replayed_state);
+        }
+        assert!(compact_trace
+            .replay_until(compact_trace.num_cycles() + 1)
+            .is_none());
+    }
+
     #[test]
     fn print_vm_state_with_long_jump_stack() {
         let labels = [
@@ -3539,4 +4940,289 @@ pub(crate) mod tests {
         let actual = public_input.iter().join(", ");
         assert_eq!("1, 2, 3", actual);
     }
+
+    #[test]
+    fn stepping_through_stack_neutral_block_restores_stack_depth() {
+        let program = triton_program!(push 1 push 2 add pop 1 halt);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        let initial_depth = vm_state.op_stack.len();
+
+        let outcome = vm_state.step_until_stack_depth(initial_depth, 10).unwrap();
+        assert!(StackDepthOutcome::ReachedTarget == outcome);
+        assert!(4 == vm_state.cycle_count);
+        assert!(!vm_state.halting);
+    }
+
+    #[test]
+    fn stepping_until_unreachable_stack_depth_stops_at_halt() {
+        let program = triton_program!(push 1 push 2 halt);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        let unreachable_depth = vm_state.op_stack.len() + 5;
+
+        let outcome = vm_state
+            .step_until_stack_depth(unreachable_depth, 10)
+            .unwrap();
+        assert!(StackDepthOutcome::Halted == outcome);
+        assert!(vm_state.halting);
+    }
+
+    #[test]
+    fn stepping_until_stack_depth_respects_step_budget() {
+        let program = triton_program!(push 1 push 2 push 3 pop 3 halt);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        let initial_depth = vm_state.op_stack.len();
+
+        let outcome = vm_state.step_until_stack_depth(initial_depth, 2).unwrap();
+        assert!(StackDepthOutcome::ExhaustedBudget == outcome);
+        assert!(2 == vm_state.cycle_count);
+
+        let outcome = vm_state.step_until_stack_depth(initial_depth, 2).unwrap();
+        assert!(StackDepthOutcome::ReachedTarget == outcome);
+    }
+
+    #[test]
+    fn running_until_predicate_stops_as_soon_as_predicate_holds() {
+        let program = triton_program!(push 1 push 2 push 3 pop 3 halt);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        let has_pushed_2 = |state: &VMState| state.op_stack[0] == bfe!(2);
+
+        let_assert!(Ok(outcome) = vm_state.run_until(has_pushed_2, 10));
+        assert!(RunOutcome::PredicateMet == outcome);
+        assert!(2 == vm_state.cycle_count);
+    }
+
+    #[test]
+    fn running_until_predicate_respects_cycle_budget() {
+        let program = triton_program!(call loop halt loop: push 1 pop 1 recurse);
+        let mut vm_state = VMState::new(program.clone(), [].into(), [].into());
+
+        let_assert!(Ok(outcome) = vm_state.run_until(|_| false, 7));
+        assert!(RunOutcome::CycleBudgetExhausted == outcome);
+        assert!(7 == vm_state.cycle_count);
+
+        let mut expected_state = VMState::new(program, [].into(), [].into());
+        for _ in 0..7 {
+            let_assert!(Ok(_) = expected_state.step());
+        }
+        assert!(expected_state == vm_state);
+
+        let_assert!(Ok(outcome) = vm_state.run_until(|_| false, 0));
+        assert!(RunOutcome::CycleBudgetExhausted == outcome);
+        assert!(7 == vm_state.cycle_count);
+    }
+
+    #[test]
+    fn running_until_predicate_reports_halting() {
+        let program = triton_program!(push 1 pop 1 halt);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        let_assert!(Ok(outcome) = vm_state.run_until(|_| false, 10));
+        assert!(RunOutcome::Halted == outcome);
+        assert!(vm_state.halting);
+
+        let_assert!(Ok(outcome) = vm_state.run_until(|_| true, 10));
+        assert!(RunOutcome::Halted == outcome);
+    }
+
+    #[test]
+    fn stepping_until_stack_depth_propagates_errors() {
+        let program = triton_program!(push 0 assert halt);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        let initial_depth = vm_state.op_stack.len();
+
+        let_assert!(Err(err) = vm_state.step_until_stack_depth(initial_depth, 10));
+        let_assert!(InstructionError::AssertionFailed(_) = err);
+    }
+
+    #[test]
+    fn dry_run_supplies_missing_input_on_demand() {
+        let program = triton_program! {
+            read_io 2 add divine 1 mul write_io 1
+            push 0 push 0 push 0 push 0 push 0 push 0 merkle_step
+            halt
+        };
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        let mut requests = vec![];
+        let mut next_element = bfe!(1);
+        let_assert!(
+            Ok(()) = vm_state.dry_run(|missing_input| {
+                requests.push(missing_input);
+                next_element += bfe!(1);
+                next_element - bfe!(1)
+            })
+        );
+
+        assert!(bfe_vec![9] == vm_state.public_output);
+        let expected_requests = [
+            [MissingInput::PublicInput; 2].as_slice(),
+            &[MissingInput::SecretInput],
+            &[MissingInput::SecretDigest; Digest::LEN],
+        ]
+        .concat();
+        assert!(expected_requests == requests);
+    }
+
+    #[test]
+    fn dry_run_with_sufficient_input_agrees_with_regular_run() {
+        let program = triton_program!(read_io 2 add divine 1 mul write_io 1 halt);
+        let public_input = PublicInput::new(bfe_vec![1, 2]);
+        let non_determinism = NonDeterminism::new(bfe_vec![3]);
+        let_assert!(
+            Ok(output) = VM::run(
+                program.clone(),
+                public_input.clone(),
+                non_determinism.clone()
+            )
+        );
+
+        let mut vm_state = VMState::new(program, public_input, non_determinism);
+        let_assert!(Ok(()) = vm_state.dry_run(|_| panic!("no input should be missing")));
+        assert!(output == vm_state.public_output);
+    }
+
+    #[test]
+    fn dry_run_propagates_errors_unrelated_to_input() {
+        let program = triton_program!(push 0 assert halt);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        let_assert!(Err(err) = vm_state.dry_run(|_| bfe!(0)));
+        let_assert!(InstructionError::AssertionFailed(_) = err);
+    }
+
+    #[test]
+    fn public_input_progress_reflects_reads() {
+        let program = triton_program!(read_io 3 read_io 1 halt);
+        let public_input = PublicInput::new(bfe_vec![1, 2, 3, 4, 5]);
+        let mut vm_state = VMState::new(program, public_input, [].into());
+        assert!((0, 5) == vm_state.public_input_progress());
+
+        let_assert!(Ok(_) = vm_state.step());
+        assert!((3, 5) == vm_state.public_input_progress());
+
+        let_assert!(Ok(()) = vm_state.run());
+        assert!((4, 5) == vm_state.public_input_progress());
+    }
+
+    #[test]
+    fn reading_past_end_of_public_input_consumes_nothing() {
+        let program = triton_program!(read_io 2 read_io 2 halt);
+        let public_input = PublicInput::new(bfe_vec![1, 2, 3]);
+        let mut vm_state = VMState::new(program, public_input, [].into());
+
+        let_assert!(Err(err) = vm_state.run());
+        let_assert!(InstructionError::EmptyPublicInput(1) = err);
+        assert!((2, 3) == vm_state.public_input_progress());
+    }
+
+    #[test]
+    fn provenance_is_not_tracked_by_default() {
+        let program = triton_program!(push 3 push 4 add halt);
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        let_assert!(Ok(()) = vm_state.run());
+        assert!(vm_state.provenance(OpStackElement::ST0).is_none());
+    }
+
+    #[test]
+    fn provenance_points_to_last_modifying_step() {
+        let program = triton_program!(push 3 push 4 nop add push 0 add push 5 swap 3 halt);
+        let vm_state = VMState::new(program, [].into(), [].into());
+        let mut vm_state = vm_state.with_provenance_tracking();
+        let provenance = |cycle, instruction_pointer| {
+            Some(Provenance {
+                cycle,
+                instruction_pointer,
+            })
+        };
+
+        for _ in 0..4 {
+            let_assert!(Ok(_) = vm_state.step());
+        }
+        assert!(provenance(3, 5) == vm_state.provenance(OpStackElement::ST0));
+        assert!(vm_state.provenance(OpStackElement::ST1).is_none());
+
+        // adding 0 does not modify the top of the stack
+        for _ in 0..2 {
+            let_assert!(Ok(_) = vm_state.step());
+        }
+        assert!(provenance(3, 5) == vm_state.provenance(OpStackElement::ST0));
+
+        let_assert!(Ok(()) = vm_state.run());
+        assert!(provenance(7, 11) == vm_state.provenance(OpStackElement::ST0));
+        assert!(provenance(3, 5) == vm_state.provenance(OpStackElement::ST1));
+        assert!(vm_state.provenance(OpStackElement::ST2).is_none());
+        assert!(provenance(7, 11) == vm_state.provenance(OpStackElement::ST3));
+        assert!(vm_state.provenance(OpStackElement::ST4).is_none());
+    }
+
+    #[proptest(cases = 20)]
+    fn initializing_prologue_recreates_op_stack_and_ram(
+        #[strategy(vec(arb(), OpStackElement::COUNT..OpStackElement::COUNT + 20))] stack: Vec<
+            BFieldElement,
+        >,
+        #[strategy(arb())] ram: HashMap<BFieldElement, BFieldElement>,
+    ) {
+        let mut snapshot = VMState::new(triton_program!(halt), [].into(), [].into());
+        snapshot.op_stack.stack = stack;
+        snapshot.ram = ram;
+
+        let mut code = snapshot.initializing_prologue("snapshot");
+        code.extend(triton_asm!(snapshot: return));
+        let program = Program::from_code(&code.iter().join("\n")).unwrap();
+        let mut vm_state = VMState::new(program, [].into(), [].into());
+        vm_state.run().unwrap();
+
+        let non_zero = |ram: &HashMap<BFieldElement, BFieldElement>| {
+            ram.iter()
+                .filter(|(_, value)| !value.is_zero())
+                .map(|(&address, &value)| (address, value))
+                .collect::<HashMap<_, _>>()
+        };
+        assert!(snapshot.op_stack.stack == vm_state.op_stack.stack);
+        assert!(non_zero(&snapshot.ram) == non_zero(&vm_state.ram));
+    }
+
+    #[test]
+    fn initializing_prologue_of_fresh_state_ends_in_call_and_halt() {
+        let vm_state = VMState::new(triton_program!(halt), [].into(), [].into());
+        let prologue = vm_state.initializing_prologue("main");
+        let_assert!([.., call, halt] = prologue.as_slice());
+        assert!(&triton_instr!(call main) == call);
+        assert!(&triton_instr!(halt) == halt);
+    }
+
+    #[test]
+    fn public_input_can_be_parsed_from_string() {
+        let public_input = "1, 2 3,\n4".parse::<PublicInput>().unwrap();
+        assert!(bfe_vec![1, 2, 3, 4] == public_input.individual_tokens);
+
+        let public_input = "".parse::<PublicInput>().unwrap();
+        assert!(public_input.is_empty());
+    }
+
+    #[test]
+    fn parsing_non_canonical_public_input_fails_in_strict_mode() {
+        let input = format!("1, {}", BFieldElement::P + 1);
+        let_assert!(Err(err) = input.parse::<PublicInput>());
+        let_assert!(ParseBFieldElementError::NotCanonical(_) = err);
+
+        let_assert!(Err(err) = PublicInput::parse_with_mode(&input, ParseMode::Strict));
+        let_assert!(ParseBFieldElementError::NotCanonical(_) = err);
+    }
+
+    #[test]
+    fn parsing_non_canonical_public_input_reduces_modulo_p_if_requested() {
+        let p = u128::from(BFieldElement::P);
+        let input = format!("1, {}, {}, {}", p, p + 2, u128::from(u64::MAX) + 1);
+        let public_input = PublicInput::parse_with_mode(&input, ParseMode::ReduceModP).unwrap();
+
+        let two_pow_64_mod_p = bfe!(u64::MAX) + bfe!(1);
+        let expected = vec![bfe!(1), bfe!(0), bfe!(2), two_pow_64_mod_p];
+        assert!(expected == public_input.individual_tokens);
+    }
+
+    #[test]
+    fn parsing_malformed_public_input_fails_in_both_modes() {
+        for mode in [ParseMode::Strict, ParseMode::ReduceModP] {
+            let_assert!(Err(err) = PublicInput::parse_with_mode("1, two, 3", mode));
+            let_assert!(ParseBFieldElementError::ParseU64Error(_) = err);
+        }
+    }
 }